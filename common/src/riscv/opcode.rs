@@ -309,7 +309,7 @@ pub enum BuiltinOpcode {
     JALR,   // Jump and link register
     ECALL,  // Environment call
     EBREAK, // Environment break       UNSUPPORTED
-    FENCE,  // Fence (memory ordering) UNSUPPORTED
+    FENCE,  // Fence (memory ordering), executed as a no-op
 
     // S-type instructions
     SB, // Store byte
@@ -0,0 +1,6 @@
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+#[nexus_rt::main]
+fn main() {
+    nexus_rt::exit(42);
+}
@@ -0,0 +1,35 @@
+use std::time::Duration;
+
+use nexus_vm_prover::trace::utils::fill_base_column_from_u32s;
+use stwo_prover::core::{backend::simd::column::BaseColumn, fields::m31::BaseField};
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+const LOG_SIZE: u32 = 20;
+
+criterion_group! {
+    name = base_column;
+    config = Criterion::default().warm_up_time(Duration::from_millis(3000));
+    targets = bench_base_column,
+}
+
+criterion_main!(base_column);
+
+fn bench_base_column(c: &mut Criterion) {
+    let data: Vec<u32> = (0..1u32 << LOG_SIZE)
+        .map(|i| i.wrapping_mul(0x9E37_79B9))
+        .collect();
+
+    let mut group = c.benchmark_group(format!("BaseColumn-LogSize-{LOG_SIZE}"));
+    group.bench_function("FromIter", |b| {
+        b.iter(|| {
+            black_box(BaseColumn::from_iter(
+                black_box(&data).iter().map(|&v| BaseField::from(v)),
+            ))
+        })
+    });
+    group.bench_function("FromU32s", |b| {
+        b.iter(|| black_box(fill_base_column_from_u32s(black_box(&data))))
+    });
+    group.finish();
+}
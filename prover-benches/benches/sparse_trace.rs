@@ -0,0 +1,68 @@
+use std::time::Duration;
+
+use nexus_vm_prover::{
+    column::Column,
+    trace::{SparseTracesBuilder, TracesBuilder},
+};
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+const LOG_SIZE: u32 = 20;
+
+/// Columns filled on every other row, the rest of the trace is zero.
+const FILLED_COLUMNS: &[Column] = &[Column::ValueA, Column::ValueB, Column::ValueC];
+
+criterion_group! {
+    name = sparse_trace;
+    config = Criterion::default().warm_up_time(Duration::from_millis(3000));
+    targets = bench_sparse_trace,
+}
+
+criterion_main!(sparse_trace);
+
+fn bench_sparse_trace(c: &mut Criterion) {
+    let mut group = c.benchmark_group(format!("SparseTrace-LogSize-{LOG_SIZE}"));
+    group.sample_size(10);
+
+    group.bench_function("DenseFillAndFinalize", |b| {
+        b.iter(|| {
+            let mut traces = TracesBuilder::new(black_box(LOG_SIZE));
+            for row in filled_rows() {
+                for &col in FILLED_COLUMNS {
+                    traces.fill_columns_bytes(row, &word(row), col);
+                }
+            }
+            black_box(traces.finalize())
+        })
+    });
+    group.bench_function("SparseFillAndFinalize", |b| {
+        b.iter(|| {
+            let mut traces = SparseTracesBuilder::new(black_box(LOG_SIZE));
+            for row in filled_rows() {
+                for &col in FILLED_COLUMNS {
+                    traces.fill_columns_bytes(row, &word(row), col);
+                }
+            }
+            black_box(traces.finalize())
+        })
+    });
+    group.finish();
+
+    let mut sparse = SparseTracesBuilder::new(LOG_SIZE);
+    for row in filled_rows() {
+        for &col in FILLED_COLUMNS {
+            sparse.fill_columns_bytes(row, &word(row), col);
+        }
+    }
+    let dense_bytes = Column::COLUMNS_NUM << LOG_SIZE << 2;
+    let sparse_bytes = sparse.num_non_zero() * std::mem::size_of::<(usize, u32)>();
+    println!("dense trace: {dense_bytes} bytes, sparse trace: ~{sparse_bytes} bytes of entries");
+}
+
+fn filled_rows() -> impl Iterator<Item = usize> {
+    (0..1 << LOG_SIZE).step_by(2)
+}
+
+fn word(row: usize) -> [u8; 4] {
+    (row as u32 | 0x0101_0101).to_le_bytes()
+}
@@ -174,12 +174,39 @@ impl MachineChip for CpuChip {
             Some(BuiltinOpcode::SRA) | Some(BuiltinOpcode::SRAI) => {
                 traces.fill_columns(row_idx, true, IsSra);
             }
+            Some(BuiltinOpcode::MUL) => {
+                traces.fill_columns(row_idx, true, IsMul);
+            }
+            Some(BuiltinOpcode::MULH) => {
+                traces.fill_columns(row_idx, true, IsMulh);
+            }
+            Some(BuiltinOpcode::MULHSU) => {
+                traces.fill_columns(row_idx, true, IsMulhsu);
+            }
+            Some(BuiltinOpcode::MULHU) => {
+                traces.fill_columns(row_idx, true, IsMulhu);
+            }
+            Some(BuiltinOpcode::DIV) => {
+                traces.fill_columns(row_idx, true, IsDiv);
+            }
+            Some(BuiltinOpcode::DIVU) => {
+                traces.fill_columns(row_idx, true, IsDivu);
+            }
+            Some(BuiltinOpcode::REM) => {
+                traces.fill_columns(row_idx, true, IsRem);
+            }
+            Some(BuiltinOpcode::REMU) => {
+                traces.fill_columns(row_idx, true, IsRemu);
+            }
             Some(BuiltinOpcode::ECALL) => {
                 traces.fill_columns(row_idx, true, IsEcall);
             }
             Some(BuiltinOpcode::EBREAK) => {
                 traces.fill_columns(row_idx, true, IsEbreak);
             }
+            Some(BuiltinOpcode::FENCE) => {
+                traces.fill_columns(row_idx, true, IsFence);
+            }
             _ => {
                 panic!(
                     "Unsupported opcode: {:?}",
@@ -342,6 +369,14 @@ impl MachineChip for CpuChip {
         let [is_sll] = trace_eval!(trace_eval, IsSll);
         let [is_srl] = trace_eval!(trace_eval, IsSrl);
         let [is_sra] = trace_eval!(trace_eval, IsSra);
+        let [is_mul] = trace_eval!(trace_eval, IsMul);
+        let [is_mulh] = trace_eval!(trace_eval, IsMulh);
+        let [is_mulhsu] = trace_eval!(trace_eval, IsMulhsu);
+        let [is_mulhu] = trace_eval!(trace_eval, IsMulhu);
+        let [is_div] = trace_eval!(trace_eval, IsDiv);
+        let [is_divu] = trace_eval!(trace_eval, IsDivu);
+        let [is_rem] = trace_eval!(trace_eval, IsRem);
+        let [is_remu] = trace_eval!(trace_eval, IsRemu);
         let [is_padding] = trace_eval!(trace_eval, IsPadding);
         let [is_sb] = trace_eval!(trace_eval, IsSb);
         let [is_sh] = trace_eval!(trace_eval, IsSh);
@@ -353,6 +388,7 @@ impl MachineChip for CpuChip {
         let [is_lw] = trace_eval!(trace_eval, IsLw);
         let [is_ecall] = trace_eval!(trace_eval, IsEcall);
         let [is_ebreak] = trace_eval!(trace_eval, IsEbreak);
+        let [is_fence] = trace_eval!(trace_eval, IsFence);
         eval.add_constraint(
             is_add.clone()
                 + is_sub.clone()
@@ -382,13 +418,22 @@ impl MachineChip for CpuChip {
                 + is_sll.clone()
                 + is_srl.clone()
                 + is_sra.clone()
+                + is_mul.clone()
+                + is_mulh.clone()
+                + is_mulhsu.clone()
+                + is_mulhu.clone()
+                + is_div.clone()
+                + is_divu.clone()
+                + is_rem.clone()
+                + is_remu.clone()
                 + is_ecall.clone()
                 + is_ebreak.clone()
+                + is_fence
                 + is_padding
                 - E::F::one(),
         );
 
-        // is_type_r = (1-imm_c) ・(is_add + is_sub + is_slt + is_sltu + is_xor + is_or + is_and + is_sll + is_srl + is_sra)
+        // is_type_r = (1-imm_c) ・(is_add + is_sub + is_slt + is_sltu + is_xor + is_or + is_and + is_sll + is_srl + is_sra + is_mul + is_mulh + is_mulhsu + is_mulhu + is_div + is_divu + is_rem + is_remu)
         let [is_type_r] = virtual_column::IsTypeR::eval(trace_eval);
 
         // is_type_i = is_load + is_jalr + is_alu_imm_no_shift + is_alu_imm_shift
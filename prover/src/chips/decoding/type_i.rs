@@ -12,9 +12,9 @@ use crate::{
 use nexus_vm::riscv::InstructionType::{IType, ITypeShamt};
 
 use crate::column::Column::{
-    self, ImmC, InstrVal, IsAdd, IsAnd, IsJalr, IsLb, IsLbu, IsLh, IsLhu, IsLw, IsOr, IsSll, IsSlt,
-    IsSltu, IsSra, IsSrl, IsXor, OpA, OpA0, OpA1_4, OpB, OpB0, OpB1_4, OpC, OpC0_3, OpC11, OpC4,
-    OpC4_7, OpC8_10, ValueC,
+    self, ImmC, InstrVal, IsAdd, IsAnd, IsFence, IsJalr, IsLb, IsLbu, IsLh, IsLhu, IsLw, IsOr,
+    IsSll, IsSlt, IsSltu, IsSra, IsSrl, IsXor, OpA, OpA0, OpA1_4, OpB, OpB0, OpB1_4, OpC, OpC0_3,
+    OpC11, OpC4, OpC4_7, OpC8_10, ValueC,
 };
 
 use crate::trace::eval::trace_eval;
@@ -160,7 +160,15 @@ impl MachineChip for TypeINoShiftChip {
         // (is_jalr) ・(b1100111 + op_a0・2^7 - instr_val_1) = 0
         eval.add_constraint(
             is_jalr.clone()
-                * (E::F::from(BaseField::from(0b1100111)) + op_a0 * BaseField::from(1 << 7)
+                * (E::F::from(BaseField::from(0b1100111))
+                    + op_a0.clone() * BaseField::from(1 << 7)
+                    - instr_val_1.clone()),
+        );
+        // (is_fence) ・(b0001111 + op_a0・2^7 - instr_val_1) = 0
+        let [is_fence] = trace_eval!(trace_eval, IsFence);
+        eval.add_constraint(
+            is_fence.clone()
+                * (E::F::from(BaseField::from(0b0001111)) + op_a0 * BaseField::from(1 << 7)
                     - instr_val_1),
         );
         // (is_lb)・(op_a1_4 + b000・2^4 + op_b0・2^7 - instr_val_2) = 0
@@ -265,6 +273,11 @@ impl MachineChip for TypeINoShiftChip {
             is_jalr
                 * (op_a1_4.clone() + op_b0.clone() * BaseField::from(1 << 7) - instr_val_2.clone()),
         );
+        // (is_fence)・(op_a1_4 + b000・2^4 + op_b0・2^7 - instr_val_2) = 0
+        eval.add_constraint(
+            is_fence
+                * (op_a1_4.clone() + op_b0.clone() * BaseField::from(1 << 7) - instr_val_2.clone()),
+        );
 
         // (is_type_i_no_shift)・(op_b1_4 + op_c0_3・2^4 - instr_val_3) = 0
         eval.add_constraint(
@@ -435,3 +448,69 @@ impl MachineChip for TypeIShiftChip {
         );
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        chips::{
+            AddChip, CpuChip, DecodingCheckChip, ProgramMemCheckChip, RangeCheckChip,
+            RegisterMemCheckChip,
+        },
+        test_utils::assert_chip,
+        trace::{
+            program::iter_program_steps, program_trace::ProgramTracesBuilder, PreprocessedTraces,
+        },
+    };
+
+    use super::*;
+    use nexus_vm::{
+        emulator::InternalView,
+        riscv::{BasicBlock, BuiltinOpcode, Instruction, Opcode},
+        trace::k_trace_direct,
+    };
+
+    const LOG_SIZE: u32 = PreprocessedTraces::MIN_LOG_SIZE;
+
+    fn setup_basic_block_ir() -> Vec<BasicBlock> {
+        let basic_block = BasicBlock::new(vec![
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 1, 0, 1),
+            // fence iorw, iorw
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::FENCE), 0, 0, 0xff),
+            // fence.tso, the immediate has its sign bit set
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::FENCE), 0, 0, 0x833),
+            // rd and rs1 are reserved, FENCE neither reads nor writes them
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::FENCE), 1, 1, 0x33),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 2, 1, 1),
+        ]);
+        vec![basic_block]
+    }
+
+    #[test]
+    fn test_k_trace_decode_fence_instructions() {
+        type Chips = (
+            CpuChip,
+            DecodingCheckChip,
+            AddChip,
+            RegisterMemCheckChip,
+            ProgramMemCheckChip,
+            RangeCheckChip,
+        );
+        let basic_block = setup_basic_block_ir();
+        let k = 1;
+
+        // Get traces from VM K-Trace interface
+        let (view, vm_traces) = k_trace_direct(&basic_block, k).expect("Failed to create trace");
+        let program_info = view.get_program_memory();
+
+        // Trace circuit
+        let mut traces = TracesBuilder::new(LOG_SIZE);
+        let program_steps = iter_program_steps(&vm_traces, traces.num_rows());
+        let program_traces = ProgramTracesBuilder::new_with_empty_memory(LOG_SIZE, program_info);
+        let mut side_note = SideNote::new(&program_traces, &view);
+
+        for (row_idx, program_step) in program_steps.enumerate() {
+            Chips::fill_main_trace(&mut traces, row_idx, &program_step, &mut side_note);
+        }
+        assert_chip::<Chips>(traces, Some(program_traces.finalize()));
+    }
+}
@@ -132,6 +132,10 @@ impl MachineChip for AddChip {
                         + carry_flag[0].clone())),
         );
     }
+
+    fn owned_columns() -> Vec<Column> {
+        vec![IsAdd, CarryFlag, ValueA, ValueB, ValueC]
+    }
 }
 
 #[cfg(test)]
@@ -142,7 +146,7 @@ mod test {
             CpuChip, DecodingCheckChip, ProgramMemCheckChip, RangeCheckChip, RegisterMemCheckChip,
             TimestampChip,
         },
-        test_utils::assert_chip,
+        test_utils::{assert_chip, assert_chip_isolated},
         trace::{
             program::iter_program_steps, program_trace::ProgramTracesBuilder, PreprocessedTraces,
         },
@@ -207,17 +211,17 @@ mod test {
         vec![basic_block, basic_block_2]
     }
 
-    #[test]
-    fn test_k_trace_constrained_add_instructions() {
-        type Chips = (
-            CpuChip,
-            DecodingCheckChip,
-            AddChip,
-            RegisterMemCheckChip,
-            ProgramMemCheckChip,
-            TimestampChip,
-            RangeCheckChip,
-        );
+    type Chips = (
+        CpuChip,
+        DecodingCheckChip,
+        AddChip,
+        RegisterMemCheckChip,
+        ProgramMemCheckChip,
+        TimestampChip,
+        RangeCheckChip,
+    );
+
+    fn fill_add_traces() -> (TracesBuilder, ProgramTracesBuilder) {
         let basic_block = setup_basic_block_ir();
         let k = 1;
 
@@ -234,6 +238,30 @@ mod test {
         for (row_idx, program_step) in program_steps.enumerate() {
             Chips::fill_main_trace(&mut traces, row_idx, &program_step, &mut side_note);
         }
+        (traces, program_trace)
+    }
+
+    #[test]
+    fn test_k_trace_constrained_add_instructions() {
+        let (traces, program_trace) = fill_add_traces();
         assert_chip::<Chips>(traces, Some(program_trace.finalize()));
     }
+
+    #[test]
+    fn test_add_chip_isolated() {
+        let (traces, program_trace) = fill_add_traces();
+        assert_chip_isolated::<AddChip>(traces, Some(program_trace.finalize()));
+    }
+
+    #[cfg(feature = "debug-tools")]
+    #[test]
+    fn test_add_trace_generation_is_deterministic() {
+        let (expected, _) = fill_add_traces();
+        let (traces, program_trace) = fill_add_traces();
+        crate::test_utils::assert_chip_with_expected_trace::<Chips>(
+            traces,
+            &expected,
+            Some(program_trace.finalize()),
+        );
+    }
 }
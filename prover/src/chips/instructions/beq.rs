@@ -201,13 +201,9 @@ impl MachineChip for BeqChip {
         );
 
         // is_beq・(neq_12_flag)・(1-neq_12_flag) = 0
-        eval.add_constraint(
-            is_beq.clone() * neq_12_flag[0].clone() * (E::F::one() - neq_12_flag[0].clone()),
-        );
+        trace_eval.assert_boolean_or_zero(eval, Column::Neq12, is_beq.clone());
         // is_beq・(neq_34_flag)・(1-neq_34_flag) = 0
-        eval.add_constraint(
-            is_beq.clone() * neq_34_flag[0].clone() * (E::F::one() - neq_34_flag[0].clone()),
-        );
+        trace_eval.assert_boolean_or_zero(eval, Column::Neq34, is_beq.clone());
 
         // Enforcing neq_flag_aux_i ≠ 0
         // is_beq・(neq_12_flag_aux・neq_12_flag_aux_inv - 1) = 0
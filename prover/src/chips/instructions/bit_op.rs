@@ -2,7 +2,7 @@ use std::array;
 
 use num_traits::Zero;
 use stwo_prover::{
-    constraint_framework::{logup::LogupTraceGenerator, EvalAtRow, Relation, RelationEntry},
+    constraint_framework::{EvalAtRow, Relation, RelationEntry},
     core::{
         backend::simd::m31::{PackedBaseField, LOG_N_LANES},
         fields::m31::BaseField,
@@ -20,7 +20,8 @@ use crate::{
         eval::{trace_eval, TraceEval},
         program_trace::ProgramTraces,
         sidenote::SideNote,
-        FinalizedTraces, PreprocessedTraces, ProgramStep, TracesBuilder, Word,
+        FinalizedTraces, InteractionTraceBuilder, PreprocessedTraces, ProgramStep, TracesBuilder,
+        Word,
     },
     traits::{ExecuteChip, MachineChip},
     virtual_column::{VirtualColumn, VirtualColumnForSum},
@@ -323,7 +324,7 @@ impl MachineChip for BitOpChip {
     ///
     /// data[vec_row] contains sixteen rows. A single write_frac() adds sixteen rows.
     fn fill_interaction_trace(
-        logup_trace_gen: &mut LogupTraceGenerator,
+        logup_trace_gen: &mut InteractionTraceBuilder,
         original_traces: &FinalizedTraces,
         _preprocessed_trace: &PreprocessedTraces,
         _program_traces: &ProgramTraces,
@@ -200,13 +200,9 @@ impl MachineChip for BneChip {
         );
 
         // is_bne・(neq_12_flag)・(1-neq_12_flag) = 0
-        eval.add_constraint(
-            is_bne.clone() * neq_12_flag[0].clone() * (E::F::one() - neq_12_flag[0].clone()),
-        );
+        trace_eval.assert_boolean_or_zero(eval, Column::Neq12, is_bne.clone());
         // is_bne・(neq_34_flag)・(1-neq_34_flag) = 0
-        eval.add_constraint(
-            is_bne.clone() * neq_34_flag[0].clone() * (E::F::one() - neq_34_flag[0].clone()),
-        );
+        trace_eval.assert_boolean_or_zero(eval, Column::Neq34, is_bne.clone());
 
         // Enforcing neq_flag_aux_i ≠ 0
         // is_bne・(neq_12_flag_aux・neq_12_flag_aux_inv - 1) = 0
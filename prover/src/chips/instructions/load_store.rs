@@ -1,7 +1,7 @@
 use nexus_vm::{memory::MemAccessSize, riscv::BuiltinOpcode, WORD_SIZE};
 use num_traits::{One, Zero};
 use stwo_prover::{
-    constraint_framework::{logup::LogupTraceGenerator, EvalAtRow, Relation, RelationEntry},
+    constraint_framework::{EvalAtRow, Relation, RelationEntry},
     core::{
         backend::simd::m31::{PackedBaseField, LOG_N_LANES},
         fields::m31::{self, BaseField},
@@ -24,7 +24,8 @@ use crate::{
         eval::{preprocessed_trace_eval, program_trace_eval, trace_eval},
         program_trace::ProgramTraces,
         sidenote::SideNote,
-        FinalizedTraces, PreprocessedTraces, ProgramStep, TracesBuilder, Word,
+        FinalizedTraces, InteractionTraceBuilder, PreprocessedTraces, ProgramStep, TracesBuilder,
+        Word,
     },
     traits::MachineChip,
     virtual_column::{IsLoad, IsTypeS, VirtualColumn, VirtualColumnForSum},
@@ -86,7 +87,7 @@ impl MachineChip for LoadStoreChip {
     }
 
     fn fill_interaction_trace(
-        logup_trace_gen: &mut LogupTraceGenerator,
+        logup_trace_gen: &mut InteractionTraceBuilder,
         original_traces: &FinalizedTraces,
         preprocessed_trace: &PreprocessedTraces,
         program_traces: &ProgramTraces,
@@ -754,7 +755,7 @@ impl LoadStoreChip {
         original_traces: &FinalizedTraces,
         program_traces: &ProgramTraces,
         lookup_element: &LoadStoreLookupElements,
-        logup_trace_gen: &mut LogupTraceGenerator,
+        logup_trace_gen: &mut InteractionTraceBuilder,
     ) {
         let [ram_init_final_flag] = original_traces.get_base_column(Column::RamInitFinalFlag);
         let ram_init_final_addr =
@@ -815,7 +816,7 @@ impl LoadStoreChip {
         original_traces: &FinalizedTraces,
         preprocessed_traces: &PreprocessedTraces,
         lookup_elements: &LoadStoreLookupElements,
-        logup_trace_gen: &mut LogupTraceGenerator,
+        logup_trace_gen: &mut InteractionTraceBuilder,
         val_prev: Column,
         ts_prev: Column,
         val_cur: Column,
@@ -868,7 +869,7 @@ impl LoadStoreChip {
     fn subtract_access<Accessed: VirtualColumn<1>>(
         original_traces: &FinalizedTraces,
         lookup_elements: &LoadStoreLookupElements,
-        logup_trace_gen: &mut LogupTraceGenerator,
+        logup_trace_gen: &mut InteractionTraceBuilder,
         val_prev: Column,
         ts_prev: Column,
         address_offset: u8,
@@ -938,7 +939,7 @@ impl LoadStoreChip {
         original_traces: &FinalizedTraces,
         preprocessed_traces: &PreprocessedTraces,
         lookup_elements: &LoadStoreLookupElements,
-        logup_trace_gen: &mut LogupTraceGenerator,
+        logup_trace_gen: &mut InteractionTraceBuilder,
         val_cur: Column,
         address_offset: u8,
     ) {
@@ -1010,7 +1011,7 @@ impl LoadStoreChip {
     fn subtract_final_values(
         original_traces: &FinalizedTraces,
         lookup_elements: &LoadStoreLookupElements,
-        logup_trace_gen: &mut LogupTraceGenerator,
+        logup_trace_gen: &mut InteractionTraceBuilder,
     ) {
         let [ram_init_final_flag] = original_traces.get_base_column(Column::RamInitFinalFlag);
         let ram_init_final_addr =
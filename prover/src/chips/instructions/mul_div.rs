@@ -0,0 +1,814 @@
+use num_traits::{One, Zero};
+use stwo_prover::{
+    constraint_framework::EvalAtRow,
+    core::fields::{m31::BaseField, FieldExpOps},
+};
+
+use nexus_vm::{riscv::BuiltinOpcode, WORD_SIZE};
+
+use crate::{
+    column::Column::*,
+    components::AllLookupElements,
+    trace::{
+        eval::{trace_eval, TraceEval},
+        sidenote::SideNote,
+        ProgramStep, TracesBuilder, Word,
+    },
+    traits::{ExecuteChip, MachineChip},
+};
+
+/// Number of carries of the byte-wise product, the carry out of the byte position 6 is the byte 7 of the product.
+const NUM_MUL_CARRIES: usize = 2 * WORD_SIZE - 2;
+
+// Support MUL, MULH, MULHSU, MULHU, DIV, DIVU, REM and REMU opcodes.
+//
+// The high word of a signed product is derived from the unsigned product of the operand words:
+// for b = B - sgn_b・2^32 and c = C - sgn_c・2^32, the upper word of b・c is prod_high - sgn_b・C - sgn_c・B mod 2^32.
+//
+// Signed division is checked on absolute values: |b| = |c|・|qt| + |rem| with |rem| < |c|, where the quotient is
+// negative iff the operand signs differ and the remainder has the sign of the dividend, unless they are zero.
+// Unsigned division uses the same constraints with all signs set to zero.
+pub struct MulDivChip;
+
+#[derive(Default)]
+pub struct ExecutionResult {
+    result: Word,
+    prod_low: Word,
+    prod_high: Word,
+    mul_carries: [u16; NUM_MUL_CARRIES],
+    // The fields below are only used by signed opcodes.
+    sgn_b: bool,
+    sgn_c: bool,
+    // Carries of the signed high product, each of them in 0..=2 and split into two bits.
+    mulh_carries: [bool; 4],
+    // The fields below are only used by division opcodes.
+    quotient: Word,
+    remainder: Word,
+    abs_b: Word,
+    abs_c: Word,
+    abs_quotient: Word,
+    abs_remainder: Word,
+    sgn_quotient: bool,
+    sgn_remainder: bool,
+    sgn_xor: bool,
+    neg_carries: [bool; 4],
+    rem_diff: Word,
+    carry_bits: [bool; 2],
+    borrow_bits: [bool; 2],
+    div_by_zero: bool,
+    divisor_sum_inv: BaseField,
+}
+
+/// Computes the 64-bit product `x * y` byte by byte and returns its lower word, upper word
+/// and the carries of byte positions `0..6`.
+fn multiply_with_carries(x: Word, y: Word) -> (Word, Word, [u16; NUM_MUL_CARRIES]) {
+    let mut prod = [0u8; 2 * WORD_SIZE];
+    let mut carries = [0u16; NUM_MUL_CARRIES];
+    let mut carry = 0u32;
+    for k in 0..2 * WORD_SIZE - 1 {
+        let mut sum = carry;
+        for i in k.saturating_sub(WORD_SIZE - 1)..=k.min(WORD_SIZE - 1) {
+            sum += x[i] as u32 * y[k - i] as u32;
+        }
+        prod[k] = sum as u8;
+        carry = sum >> 8;
+        if k < NUM_MUL_CARRIES {
+            // The sum is bounded by 4 * 255^2 + carry, so the carry always fits into 16 bits.
+            carries[k] = carry as u16;
+        }
+    }
+    // The product doesn't exceed 64 bits, the last carry is the top byte.
+    prod[2 * WORD_SIZE - 1] = carry as u8;
+
+    let prod_low = std::array::from_fn(|i| prod[i]);
+    let prod_high = std::array::from_fn(|i| prod[WORD_SIZE + i]);
+    (prod_low, prod_high, carries)
+}
+
+/// Returns the 16-bit limb `i` of a word.
+fn half_word(word: &Word, i: usize) -> u32 {
+    word[2 * i] as u32 + ((word[2 * i + 1] as u32) << 8)
+}
+
+/// Returns the carry out of the lower half-word of `abs + value`, which equals `2^32` for a negative value.
+fn negation_carry(abs: &Word, value: &Word, sgn: bool) -> bool {
+    sgn && half_word(abs, 0) + half_word(value, 0) == 1 << 16
+}
+
+/// Returns the two's complement absolute value of a word with the given sign.
+fn abs_word(value: u32, sgn: bool) -> Word {
+    if sgn { value.wrapping_neg() } else { value }.to_le_bytes()
+}
+
+impl ExecuteChip for MulDivChip {
+    type ExecutionResult = ExecutionResult;
+
+    fn execute(program_step: &ProgramStep) -> Self::ExecutionResult {
+        let value_b = program_step.get_value_b();
+        let (value_c, _) = program_step.get_value_c();
+        let opcode = program_step.step.instruction.opcode.builtin();
+
+        match opcode {
+            Some(BuiltinOpcode::MUL) | Some(BuiltinOpcode::MULHU) => {
+                let (prod_low, prod_high, mul_carries) = multiply_with_carries(value_b, value_c);
+                ExecutionResult {
+                    result: if opcode == Some(BuiltinOpcode::MUL) {
+                        prod_low
+                    } else {
+                        prod_high
+                    },
+                    prod_low,
+                    prod_high,
+                    mul_carries,
+                    ..Default::default()
+                }
+            }
+            Some(BuiltinOpcode::MULH) | Some(BuiltinOpcode::MULHSU) => {
+                let (prod_low, prod_high, mul_carries) = multiply_with_carries(value_b, value_c);
+                let sgn_b = program_step.get_sgn_b();
+                // The second operand of MULHSU is unsigned.
+                let sgn_c = opcode == Some(BuiltinOpcode::MULH) && program_step.get_sgn_c();
+
+                let b = u32::from_le_bytes(value_b);
+                let c = u32::from_le_bytes(value_c);
+                let result = u32::from_le_bytes(prod_high)
+                    .wrapping_sub(if sgn_b { c } else { 0 })
+                    .wrapping_sub(if sgn_c { b } else { 0 })
+                    .to_le_bytes();
+
+                // result + sgn_b * c + sgn_c * b = prod_high + carry_1 * 2^32, computed by half-words
+                let sum_low = half_word(&result, 0)
+                    + sgn_b as u32 * half_word(&value_c, 0)
+                    + sgn_c as u32 * half_word(&value_b, 0);
+                let carry_0 = sum_low >> 16;
+                let sum_high = half_word(&result, 1)
+                    + sgn_b as u32 * half_word(&value_c, 1)
+                    + sgn_c as u32 * half_word(&value_b, 1)
+                    + carry_0;
+                let carry_1 = sum_high >> 16;
+                let mulh_carries = [carry_0 >= 1, carry_0 == 2, carry_1 >= 1, carry_1 == 2];
+
+                ExecutionResult {
+                    result,
+                    prod_low,
+                    prod_high,
+                    mul_carries,
+                    sgn_b,
+                    sgn_c,
+                    mulh_carries,
+                    ..Default::default()
+                }
+            }
+            Some(BuiltinOpcode::DIV)
+            | Some(BuiltinOpcode::DIVU)
+            | Some(BuiltinOpcode::REM)
+            | Some(BuiltinOpcode::REMU) => {
+                let is_signed =
+                    matches!(opcode, Some(BuiltinOpcode::DIV) | Some(BuiltinOpcode::REM));
+                let b = u32::from_le_bytes(value_b);
+                let c = u32::from_le_bytes(value_c);
+                let div_by_zero = c == 0;
+                // Division by zero results in all ones for the quotient and in the dividend for the remainder.
+                // Signed overflow of i32::MIN / -1 results in i32::MIN with remainder zero.
+                let (quotient, remainder) = if div_by_zero {
+                    (u32::MAX, b)
+                } else if is_signed {
+                    let (b, c) = (b as i32, c as i32);
+                    (b.wrapping_div(c) as u32, b.wrapping_rem(c) as u32)
+                } else {
+                    (b / c, b % c)
+                };
+
+                let sgn_b = is_signed && program_step.get_sgn_b();
+                let sgn_c = is_signed && program_step.get_sgn_c();
+                let sgn_xor = sgn_b ^ sgn_c;
+                let sgn_quotient = sgn_xor && quotient != 0;
+                let sgn_remainder = sgn_b && remainder != 0;
+
+                let (abs_b, abs_c) = (abs_word(b, sgn_b), abs_word(c, sgn_c));
+                let abs_quotient = abs_word(quotient, sgn_quotient);
+                let abs_remainder = abs_word(remainder, sgn_remainder);
+                let quotient = quotient.to_le_bytes();
+                let remainder = remainder.to_le_bytes();
+                let neg_carries = [
+                    negation_carry(&abs_b, &value_b, sgn_b),
+                    negation_carry(&abs_c, &value_c, sgn_c),
+                    negation_carry(&abs_quotient, &quotient, sgn_quotient),
+                    negation_carry(&abs_remainder, &remainder, sgn_remainder),
+                ];
+
+                let (prod_low, prod_high, mul_carries) = multiply_with_carries(abs_c, abs_quotient);
+
+                // prod_low + |rem| = |b|
+                let carry_bits = [
+                    (half_word(&prod_low, 0) + half_word(&abs_remainder, 0)) >> 16 == 1,
+                    false,
+                ];
+
+                // |rem| + rem_diff + 1 = |c|, only used when the divisor is non-zero
+                let (rem_diff, borrow_bits) = if div_by_zero {
+                    ([0u8; WORD_SIZE], [false; 2])
+                } else {
+                    let rem_diff =
+                        (u32::from_le_bytes(abs_c) - 1 - u32::from_le_bytes(abs_remainder))
+                            .to_le_bytes();
+                    let borrow =
+                        (half_word(&abs_remainder, 0) + half_word(&rem_diff, 0) + 1) >> 16 == 1;
+                    (rem_diff, [borrow, false])
+                };
+
+                let divisor_sum: u32 = abs_c.iter().map(|b| *b as u32).sum();
+                let divisor_sum_inv = if div_by_zero {
+                    BaseField::zero()
+                } else {
+                    BaseField::from(divisor_sum).inverse()
+                };
+
+                let is_div = matches!(opcode, Some(BuiltinOpcode::DIV) | Some(BuiltinOpcode::DIVU));
+                ExecutionResult {
+                    result: if is_div { quotient } else { remainder },
+                    prod_low,
+                    prod_high,
+                    mul_carries,
+                    sgn_b,
+                    sgn_c,
+                    quotient,
+                    remainder,
+                    abs_b,
+                    abs_c,
+                    abs_quotient,
+                    abs_remainder,
+                    sgn_quotient,
+                    sgn_remainder,
+                    sgn_xor,
+                    neg_carries,
+                    rem_diff,
+                    carry_bits,
+                    borrow_bits,
+                    div_by_zero,
+                    divisor_sum_inv,
+                    ..Default::default()
+                }
+            }
+            _ => panic!("Unsupported opcode for MulDivChip"),
+        }
+    }
+}
+
+impl MachineChip for MulDivChip {
+    fn fill_main_trace(
+        traces: &mut TracesBuilder,
+        row_idx: usize,
+        vm_step: &Option<ProgramStep>,
+        _side_note: &mut SideNote,
+    ) {
+        let vm_step = match vm_step {
+            Some(vm_step) => vm_step,
+            None => return, // padding
+        };
+        let opcode = vm_step.step.instruction.opcode.builtin();
+        if !matches!(
+            opcode,
+            Some(BuiltinOpcode::MUL)
+                | Some(BuiltinOpcode::MULH)
+                | Some(BuiltinOpcode::MULHSU)
+                | Some(BuiltinOpcode::MULHU)
+                | Some(BuiltinOpcode::DIV)
+                | Some(BuiltinOpcode::DIVU)
+                | Some(BuiltinOpcode::REM)
+                | Some(BuiltinOpcode::REMU)
+        ) {
+            return;
+        }
+
+        let ExecutionResult {
+            result,
+            prod_low,
+            prod_high,
+            mul_carries,
+            sgn_b,
+            sgn_c,
+            mulh_carries,
+            quotient,
+            remainder,
+            abs_b,
+            abs_c,
+            abs_quotient,
+            abs_remainder,
+            sgn_quotient,
+            sgn_remainder,
+            sgn_xor,
+            neg_carries,
+            rem_diff,
+            carry_bits,
+            borrow_bits,
+            div_by_zero,
+            divisor_sum_inv,
+        } = Self::execute(vm_step);
+
+        assert_eq!(
+            result,
+            vm_step
+                .get_result()
+                .expect("MUL/DIV instruction must have a result")
+        );
+        traces.fill_columns_bytes(row_idx, &result, ValueA);
+        traces.fill_columns_bytes(row_idx, &prod_low, ProdLow);
+        traces.fill_columns_bytes(row_idx, &prod_high, ProdHigh);
+
+        let carry_bytes: [u8; 2 * NUM_MUL_CARRIES] =
+            std::array::from_fn(|i| mul_carries[i / 2].to_le_bytes()[i % 2]);
+        for (i, col) in [MulCarry0, MulCarry1, MulCarry2].into_iter().enumerate() {
+            traces.fill_columns_bytes(
+                row_idx,
+                &carry_bytes[i * WORD_SIZE..(i + 1) * WORD_SIZE],
+                col,
+            );
+        }
+
+        if matches!(
+            opcode,
+            Some(BuiltinOpcode::MULH)
+                | Some(BuiltinOpcode::MULHSU)
+                | Some(BuiltinOpcode::DIV)
+                | Some(BuiltinOpcode::REM)
+        ) {
+            // Sign bits are the top bits of the operands, the remaining bits are range-checked by Range128Chip.
+            let mut helper_b = vm_step.get_value_b();
+            helper_b[WORD_SIZE - 1] &= 0x7f;
+            let (mut helper_c, _) = vm_step.get_value_c();
+            helper_c[WORD_SIZE - 1] &= 0x7f;
+            traces.fill_columns(row_idx, helper_b, Helper2);
+            traces.fill_columns(row_idx, helper_c, Helper3);
+        }
+        traces.fill_columns(row_idx, sgn_b, SgnB);
+        traces.fill_columns(row_idx, sgn_c, SgnC);
+        traces.fill_columns(row_idx, mulh_carries, MulhCarry);
+
+        traces.fill_columns(row_idx, quotient, Qt);
+        traces.fill_columns(row_idx, remainder, Rem);
+        traces.fill_columns(row_idx, abs_b, AbsValueB);
+        traces.fill_columns(row_idx, abs_c, AbsValueC);
+        traces.fill_columns(row_idx, abs_quotient, AbsQt);
+        traces.fill_columns(row_idx, abs_remainder, AbsRem);
+        traces.fill_columns(row_idx, sgn_quotient, SgnQt);
+        traces.fill_columns(row_idx, sgn_remainder, SgnRem);
+        traces.fill_columns(row_idx, sgn_xor, SgnBXorC);
+        traces.fill_columns(row_idx, neg_carries, NegCarry);
+        traces.fill_columns(row_idx, rem_diff, RemDiff);
+        traces.fill_columns(row_idx, carry_bits, CarryFlag);
+        traces.fill_columns(row_idx, borrow_bits, BorrowFlag);
+        traces.fill_columns(row_idx, div_by_zero, DivByZero);
+        traces.fill_columns(row_idx, divisor_sum_inv, DivisorSumInv);
+    }
+
+    fn add_constraints<E: EvalAtRow>(
+        eval: &mut E,
+        trace_eval: &TraceEval<E>,
+        _lookup_elements: &AllLookupElements,
+    ) {
+        let [is_mul] = trace_eval!(trace_eval, IsMul);
+        let [is_mulh] = trace_eval!(trace_eval, IsMulh);
+        let [is_mulhsu] = trace_eval!(trace_eval, IsMulhsu);
+        let [is_mulhu] = trace_eval!(trace_eval, IsMulhu);
+        let [is_div] = trace_eval!(trace_eval, IsDiv);
+        let [is_divu] = trace_eval!(trace_eval, IsDivu);
+        let [is_rem] = trace_eval!(trace_eval, IsRem);
+        let [is_remu] = trace_eval!(trace_eval, IsRemu);
+        // modulus for 8-bit limbs
+        let modulus = E::F::from(256u32.into());
+
+        let value_a = trace_eval!(trace_eval, ValueA);
+        let value_b = trace_eval!(trace_eval, ValueB);
+        let value_c = trace_eval!(trace_eval, ValueC);
+        let prod_low = trace_eval!(trace_eval, ProdLow);
+        let prod_high = trace_eval!(trace_eval, ProdHigh);
+
+        // Sign bits of signed operands: h2[3] + sgn_b * 2^7 = value_b[3], h3[3] + sgn_c * 2^7 = value_c[3],
+        // where h2[3] and h3[3] are range-checked to 7 bits by Range128Chip. Unsigned operands have zero signs.
+        let [sgn_b] = trace_eval!(trace_eval, SgnB);
+        let [sgn_c] = trace_eval!(trace_eval, SgnC);
+        let helper2 = trace_eval!(trace_eval, Helper2);
+        let helper3 = trace_eval!(trace_eval, Helper3);
+        let modulus_7 = E::F::from(128u32.into());
+        let is_signed_c = is_mulh.clone() + is_div.clone() + is_rem.clone();
+        let is_signed_b = is_signed_c.clone() + is_mulhsu.clone();
+        eval.add_constraint(
+            is_signed_b
+                * (helper2[3].clone() + sgn_b.clone() * modulus_7.clone() - value_b[3].clone()),
+        );
+        eval.add_constraint(
+            is_signed_c * (helper3[3].clone() + sgn_c.clone() * modulus_7 - value_c[3].clone()),
+        );
+        eval.add_constraint((is_divu.clone() + is_remu.clone()) * sgn_b.clone());
+        eval.add_constraint(
+            (is_mulhsu.clone() + is_divu.clone() + is_remu.clone()) * sgn_c.clone(),
+        );
+
+        // MUL, MULH, MULHSU and MULHU: prod_low + prod_high * 2^32 = value_b * value_c
+        let is_mul_any = is_mul.clone() + is_mulh.clone() + is_mulhsu.clone() + is_mulhu.clone();
+        constrain_product(eval, trace_eval, is_mul_any, &value_b, &value_c);
+
+        for i in 0..WORD_SIZE {
+            eval.add_constraint(is_mul.clone() * (value_a[i].clone() - prod_low[i].clone()));
+            eval.add_constraint(is_mulhu.clone() * (value_a[i].clone() - prod_high[i].clone()));
+        }
+
+        // MULH and MULHSU: value_a + sgn_b * value_c + sgn_c * value_b = prod_high (mod 2^32)
+        let is_mulh_any = is_mulh.clone() + is_mulhsu.clone();
+        let mulh_carry = trace_eval!(trace_eval, MulhCarry);
+        let half_word = |word: &[E::F; WORD_SIZE], i: usize| {
+            word[2 * i].clone() + word[2 * i + 1].clone() * modulus.clone()
+        };
+        let mut carry_in = E::F::zero();
+        for i in 0..WORD_SIZE / 2 {
+            let carry_out = mulh_carry[2 * i].clone() + mulh_carry[2 * i + 1].clone();
+            eval.add_constraint(
+                is_mulh_any.clone()
+                    * (half_word(&value_a, i)
+                        + sgn_b.clone() * half_word(&value_c, i)
+                        + sgn_c.clone() * half_word(&value_b, i)
+                        + carry_in
+                        - half_word(&prod_high, i)
+                        - carry_out.clone() * modulus.clone().pow(2)),
+            );
+            carry_in = carry_out;
+        }
+
+        // DIV, DIVU, REM and REMU: |value_b| = |value_c| * |qt| + |rem|, where |rem| < |value_c| unless value_c is zero
+        let is_div_any = is_div.clone() + is_divu.clone() + is_rem.clone() + is_remu.clone();
+        let quotient = trace_eval!(trace_eval, Qt);
+        let remainder = trace_eval!(trace_eval, Rem);
+        let abs_b = trace_eval!(trace_eval, AbsValueB);
+        let abs_c = trace_eval!(trace_eval, AbsValueC);
+        let abs_quotient = trace_eval!(trace_eval, AbsQt);
+        let abs_remainder = trace_eval!(trace_eval, AbsRem);
+        let [sgn_quotient] = trace_eval!(trace_eval, SgnQt);
+        let [sgn_remainder] = trace_eval!(trace_eval, SgnRem);
+        let [sgn_xor] = trace_eval!(trace_eval, SgnBXorC);
+        let neg_carry = trace_eval!(trace_eval, NegCarry);
+        let rem_diff = trace_eval!(trace_eval, RemDiff);
+        let carry_flag = trace_eval!(trace_eval, CarryFlag);
+        let borrow_flag = trace_eval!(trace_eval, BorrowFlag);
+        let [div_by_zero] = trace_eval!(trace_eval, DivByZero);
+        let [divisor_sum_inv] = trace_eval!(trace_eval, DivisorSumInv);
+
+        for (abs, value, sgn, carry) in [
+            (&abs_b, &value_b, &sgn_b, &neg_carry[0]),
+            (&abs_c, &value_c, &sgn_c, &neg_carry[1]),
+            (&abs_quotient, &quotient, &sgn_quotient, &neg_carry[2]),
+            (&abs_remainder, &remainder, &sgn_remainder, &neg_carry[3]),
+        ] {
+            constrain_abs(
+                eval,
+                is_div_any.clone(),
+                abs,
+                value,
+                sgn.clone(),
+                carry.clone(),
+            );
+        }
+
+        // sgn_xor = sgn_b xor sgn_c, a non-zero quotient is negative iff sgn_xor is set. For i32::MIN / -1
+        // the signs are equal, so the quotient 2^31 is stored unsigned and wraps to i32::MIN.
+        eval.add_constraint(
+            is_div_any.clone()
+                * (sgn_xor.clone() - sgn_b.clone() - sgn_c.clone()
+                    + E::F::from(2u32.into()) * sgn_b.clone() * sgn_c.clone()),
+        );
+        eval.add_constraint(
+            is_div_any.clone() * sgn_quotient.clone() * (E::F::one() - sgn_xor.clone()),
+        );
+        // A non-zero remainder has the sign of the dividend.
+        eval.add_constraint(
+            is_div_any.clone() * sgn_remainder.clone() * (E::F::one() - sgn_b.clone()),
+        );
+        for i in 0..WORD_SIZE {
+            eval.add_constraint(
+                is_div_any.clone()
+                    * (sgn_xor.clone() - sgn_quotient.clone())
+                    * abs_quotient[i].clone(),
+            );
+            eval.add_constraint(
+                is_div_any.clone()
+                    * (sgn_b.clone() - sgn_remainder.clone())
+                    * abs_remainder[i].clone(),
+            );
+        }
+
+        constrain_product(eval, trace_eval, is_div_any.clone(), &abs_c, &abs_quotient);
+
+        // The product |value_c| * |qt| must fit into 32 bits.
+        for prod_high_byte in prod_high.iter() {
+            eval.add_constraint(is_div_any.clone() * prod_high_byte.clone());
+        }
+
+        // prod_low[0] + prod_low[1] * 256 + |rem|[0] + |rem|[1] * 256 = |rs1val|[0] + |rs1val|[1] * 256 + carry[0] * 2^{16}
+        eval.add_constraint(
+            is_div_any.clone()
+                * (half_word(&prod_low, 0) + half_word(&abs_remainder, 0)
+                    - carry_flag[0].clone() * modulus.clone().pow(2)
+                    - half_word(&abs_b, 0)),
+        );
+        // prod_low[2] + prod_low[3] * 256 + |rem|[2] + |rem|[3] * 256 + carry[0] = |rs1val|[2] + |rs1val|[3] * 256
+        eval.add_constraint(
+            is_div_any.clone()
+                * (half_word(&prod_low, 1) + half_word(&abs_remainder, 1) + carry_flag[0].clone()
+                    - half_word(&abs_b, 1)),
+        );
+
+        // When the divisor is non-zero, |rem| + rem_diff + 1 = |rs2val|, so that |rem| < |rs2val|.
+        // BorrowFlag is reused as the carry flag of this addition.
+        let is_div_non_zero = is_div_any.clone() * (E::F::one() - div_by_zero.clone());
+        eval.add_constraint(
+            is_div_non_zero.clone()
+                * (half_word(&abs_remainder, 0) + half_word(&rem_diff, 0) + E::F::one()
+                    - borrow_flag[0].clone() * modulus.clone().pow(2)
+                    - half_word(&abs_c, 0)),
+        );
+        eval.add_constraint(
+            is_div_non_zero
+                * (half_word(&abs_remainder, 1) + half_word(&rem_diff, 1) + borrow_flag[0].clone()
+                    - half_word(&abs_c, 1)),
+        );
+
+        // DivByZero is set if and only if rs2val is zero. Since the bytes of |rs2val| are range-checked,
+        // their sum is zero only if every byte is zero, and |rs2val| is zero only if rs2val is.
+        let divisor_sum = abs_c
+            .iter()
+            .fold(E::F::zero(), |acc, byte| acc + byte.clone());
+        eval.add_constraint(
+            is_div_any.clone()
+                * (divisor_sum * divisor_sum_inv - E::F::one() + div_by_zero.clone()),
+        );
+        for i in 0..WORD_SIZE {
+            eval.add_constraint(is_div_any.clone() * div_by_zero.clone() * abs_c[i].clone());
+            // Division by zero results in all ones.
+            eval.add_constraint(
+                is_div_any.clone()
+                    * div_by_zero.clone()
+                    * (quotient[i].clone() - E::F::from(255u32.into())),
+            );
+        }
+
+        for i in 0..WORD_SIZE {
+            eval.add_constraint(
+                (is_div.clone() + is_divu.clone()) * (value_a[i].clone() - quotient[i].clone()),
+            );
+            eval.add_constraint(
+                (is_rem.clone() + is_remu.clone()) * (value_a[i].clone() - remainder[i].clone()),
+            );
+        }
+    }
+}
+
+/// Adds constraints for `abs = value` if `sgn` is zero and `abs + value = 2^32` otherwise, when `flag` is set.
+///
+/// The negation is checked by half-words, `carry` is the carry out of the lower half-word. A negative zero has
+/// no absolute value that fits into a word, so `sgn` must be zero for a zero value.
+fn constrain_abs<E: EvalAtRow>(
+    eval: &mut E,
+    flag: E::F,
+    abs: &[E::F; WORD_SIZE],
+    value: &[E::F; WORD_SIZE],
+    sgn: E::F,
+    carry: E::F,
+) {
+    let modulus = E::F::from(256u32.into());
+    for i in 0..WORD_SIZE {
+        eval.add_constraint(
+            flag.clone() * (E::F::one() - sgn.clone()) * (abs[i].clone() - value[i].clone()),
+        );
+    }
+    let half_word = |word: &[E::F; WORD_SIZE], i: usize| {
+        word[2 * i].clone() + word[2 * i + 1].clone() * modulus.clone()
+    };
+    eval.add_constraint(
+        flag.clone()
+            * sgn.clone()
+            * (half_word(abs, 0) + half_word(value, 0) - carry.clone() * modulus.clone().pow(2)),
+    );
+    eval.add_constraint(
+        flag * sgn * (half_word(abs, 1) + half_word(value, 1) + carry - modulus.clone().pow(2)),
+    );
+}
+
+/// Adds constraints for `prod_low + prod_high * 2^32 = x * y` when `flag` is set.
+///
+/// For every byte position k, `sum(x[i] * y[j], i + j = k) + carry[k - 1] = prod[k] + carry[k] * 256`,
+/// where the carries are stored as pairs of range-checked bytes in [`MulCarry0`]..=[`MulCarry2`]
+/// and the carry out of the position 6 is the top byte of the product.
+fn constrain_product<E: EvalAtRow>(
+    eval: &mut E,
+    trace_eval: &TraceEval<E>,
+    flag: E::F,
+    x: &[E::F; WORD_SIZE],
+    y: &[E::F; WORD_SIZE],
+) {
+    let modulus = E::F::from(256u32.into());
+    let prod_low = trace_eval!(trace_eval, ProdLow);
+    let prod_high = trace_eval!(trace_eval, ProdHigh);
+    let prod: Vec<E::F> = prod_low.into_iter().chain(prod_high).collect();
+
+    let carry_bytes: Vec<E::F> = [MulCarry0, MulCarry1, MulCarry2]
+        .into_iter()
+        .flat_map(|col| trace_eval.column_eval::<WORD_SIZE>(col))
+        .collect();
+    let carries: Vec<E::F> = carry_bytes
+        .chunks_exact(2)
+        .map(|bytes| bytes[0].clone() + bytes[1].clone() * modulus.clone())
+        .collect();
+
+    for k in 0..2 * WORD_SIZE - 1 {
+        let mut sum = if k == 0 {
+            E::F::zero()
+        } else {
+            carries[k - 1].clone()
+        };
+        for i in k.saturating_sub(WORD_SIZE - 1)..=k.min(WORD_SIZE - 1) {
+            sum += x[i].clone() * y[k - i].clone();
+        }
+        let carry_out = if k < NUM_MUL_CARRIES {
+            carries[k].clone()
+        } else {
+            prod[k + 1].clone()
+        };
+        eval.add_constraint(flag.clone() * (sum - prod[k].clone() - carry_out * modulus.clone()));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        chips::{
+            AddChip, CpuChip, DecodingCheckChip, LuiChip, ProgramMemCheckChip, RangeCheckChip,
+            RegisterMemCheckChip, SubChip, TimestampChip,
+        },
+        machine::{BaseComponent, Machine},
+        test_utils::assert_chip,
+        trace::{
+            program::iter_program_steps, program_trace::ProgramTracesBuilder, PreprocessedTraces,
+        },
+    };
+
+    use super::*;
+    use nexus_vm::{
+        emulator::InternalView,
+        riscv::{BasicBlock, BuiltinOpcode, Instruction, Opcode},
+        trace::k_trace_direct,
+    };
+
+    const LOG_SIZE: u32 = PreprocessedTraces::MIN_LOG_SIZE;
+
+    fn setup_basic_block_ir() -> Vec<BasicBlock> {
+        let basic_block = BasicBlock::new(vec![
+            // x1 = 0xFFFFF7FF, x2 = 7, x3 = 0x7FFFF000
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::LUI), 1, 0, 0xFFFFF),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 1, 1, 0x7FF),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 2, 0, 7),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::LUI), 3, 0, 0x7FFFF),
+            // MUL
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::MUL), 4, 1, 2),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::MUL), 4, 1, 1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::MUL), 4, 3, 0),
+            // MULHU
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::MULHU), 5, 1, 2),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::MULHU), 5, 1, 1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::MULHU), 5, 3, 1),
+            // DIVU
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::DIVU), 6, 1, 2),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::DIVU), 6, 2, 1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::DIVU), 6, 1, 1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::DIVU), 6, 3, 0),
+            // REMU
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::REMU), 7, 1, 2),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::REMU), 7, 2, 1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::REMU), 7, 1, 3),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::REMU), 7, 3, 0),
+            // x8 = i32::MIN, x9 = -1
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::LUI), 8, 0, 0x80000),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 9, 0, 1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SUB), 9, 0, 9),
+            // MULH
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::MULH), 10, 1, 2),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::MULH), 10, 1, 1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::MULH), 10, 3, 3),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::MULH), 10, 8, 9),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::MULH), 10, 8, 8),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::MULH), 10, 9, 0),
+            // MULHSU
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::MULHSU), 11, 1, 2),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::MULHSU), 11, 2, 1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::MULHSU), 11, 1, 1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::MULHSU), 11, 8, 9),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::MULHSU), 11, 9, 9),
+            // DIV
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::DIV), 12, 1, 2),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::DIV), 12, 2, 1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::DIV), 12, 1, 1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::DIV), 12, 8, 2),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::DIV), 12, 3, 8),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::DIV), 12, 0, 9),
+            // i32::MIN / -1 overflows to i32::MIN
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::DIV), 12, 8, 9),
+            // Division by zero
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::DIV), 12, 1, 0),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::DIV), 12, 2, 0),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::DIV), 12, 0, 0),
+            // REM
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::REM), 13, 1, 2),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::REM), 13, 2, 1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::REM), 13, 1, 3),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::REM), 13, 8, 2),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::REM), 13, 8, 1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::REM), 13, 8, 9),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::REM), 13, 1, 0),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::REM), 13, 2, 0),
+            // Results written to x0 are discarded
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::MUL), 0, 1, 1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::DIVU), 0, 1, 2),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::MULH), 0, 1, 1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::REM), 0, 1, 2),
+        ]);
+        vec![basic_block]
+    }
+    #[test]
+    fn test_multiply_with_carries() {
+        for (x, y) in [
+            (0u32, 0u32),
+            (u32::MAX, u32::MAX),
+            (0xFFFFF7FF, 7),
+            (0x12345678, 0x9ABCDEF0),
+        ] {
+            let (prod_low, prod_high, _) = multiply_with_carries(x.to_le_bytes(), y.to_le_bytes());
+            let prod = x as u64 * y as u64;
+            assert_eq!(u32::from_le_bytes(prod_low), prod as u32);
+            assert_eq!(u32::from_le_bytes(prod_high), (prod >> 32) as u32);
+        }
+    }
+
+    #[test]
+    fn test_k_trace_constrained_mul_div_instructions() {
+        type Chips = (
+            CpuChip,
+            DecodingCheckChip,
+            AddChip,
+            LuiChip,
+            SubChip,
+            MulDivChip,
+            RegisterMemCheckChip,
+            ProgramMemCheckChip,
+            TimestampChip,
+            RangeCheckChip,
+        );
+        let basic_block = setup_basic_block_ir();
+        let k = 1;
+
+        // Get traces from VM K-Trace interface
+        let (view, vm_traces) = k_trace_direct(&basic_block, k).expect("Failed to create trace");
+        let program_info = view.get_program_memory();
+
+        // Trace circuit
+        let mut traces = TracesBuilder::new(LOG_SIZE);
+        let program_steps = iter_program_steps(&vm_traces, traces.num_rows());
+        let program_trace = ProgramTracesBuilder::new_with_empty_memory(LOG_SIZE, program_info);
+        let mut side_note = SideNote::new(&program_trace, &view);
+
+        for (row_idx, program_step) in program_steps.enumerate() {
+            Chips::fill_main_trace(&mut traces, row_idx, &program_step, &mut side_note);
+        }
+        assert_chip::<Chips>(traces, Some(program_trace.finalize()));
+    }
+
+    #[test]
+    fn test_abs_word() {
+        for value in [0u32, 1, 7, 0x7FFFFFFF, 0x80000000, u32::MAX] {
+            let sgn = (value as i32) < 0;
+            let abs = abs_word(value, sgn);
+            assert_eq!(u32::from_le_bytes(abs), (value as i32).unsigned_abs());
+            // abs + value = 2^32 for negative values
+            assert_eq!(
+                negation_carry(&abs, &value.to_le_bytes(), sgn),
+                sgn && value as u16 != 0
+            );
+        }
+    }
+
+    #[test]
+    fn test_prove_verify_mul_div_instructions() {
+        let basic_block = setup_basic_block_ir();
+        let (view, program_trace) =
+            k_trace_direct(&basic_block, 1).expect("Failed to create trace");
+
+        let proof = Machine::<BaseComponent>::prove(&program_trace, &view).unwrap();
+        Machine::<BaseComponent>::verify(
+            proof,
+            view.get_program_memory(),
+            &[],
+            view.get_initial_memory(),
+            view.get_exit_code(),
+            view.get_public_output(),
+        )
+        .unwrap();
+    }
+}
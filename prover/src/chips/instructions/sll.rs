@@ -262,6 +262,10 @@ mod test {
             Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 7, 0, 1),
             // x8 = x7 << 31 (1 << 31 = 2147483648, which is 2^31)
             Instruction::new_ir(Opcode::from(BuiltinOpcode::SLLI), 8, 7, 31),
+            // x9 = x5 << 15 (-5 << 15 = 0xFFFD8000)
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SLLI), 9, 5, 15),
+            // x9 = x5 << 31 (-5 << 31 = 0x80000000)
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SLLI), 9, 5, 31),
         ]);
         vec![basic_block]
     }
@@ -281,6 +281,18 @@ mod test {
             Instruction::new_ir(Opcode::from(BuiltinOpcode::SUB), 16, 0, 1),
             // x17 = 1 because -2147483648 < -1
             Instruction::new_ir(Opcode::from(BuiltinOpcode::SLT), 17, 15, 16),
+            // x18 = 0 because -1 < -2147483648 doesn't hold
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SLT), 18, 16, 15),
+            // x19 = 1 because -2147483648 < 2000
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SLT), 19, 15, 1),
+            // x20 = 0 because -2147483648 < -2147483648 doesn't hold
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SLT), 20, 15, 15),
+            // Set x21 = 0x7FFFFFFF (largest positive 32-bit number)
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADD), 21, 15, 16),
+            // x22 = 1 because -2147483648 < 2147483647, despite the difference overflowing
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SLT), 22, 15, 21),
+            // x22 = 0 because 2147483647 < -2147483648 doesn't hold
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SLT), 22, 21, 15),
         ]);
         vec![basic_block]
     }
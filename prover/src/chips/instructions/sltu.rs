@@ -126,7 +126,9 @@ impl MachineChip for SltuChip {
 #[cfg(test)]
 mod test {
     use crate::{
-        chips::{AddChip, CpuChip, DecodingCheckChip, ProgramMemCheckChip, RegisterMemCheckChip},
+        chips::{
+            AddChip, CpuChip, DecodingCheckChip, ProgramMemCheckChip, RegisterMemCheckChip, SubChip,
+        },
         test_utils::assert_chip,
         trace::{
             preprocessed::PreprocessedBuilder, program::iter_program_steps,
@@ -172,6 +174,18 @@ mod test {
             Instruction::new_ir(Opcode::from(BuiltinOpcode::SLTIU), 3, 4, 15),
             // x3 = 0 because 10 < 5 (immediate) doesn't hold
             Instruction::new_ir(Opcode::from(BuiltinOpcode::SLTIU), 3, 4, 5),
+            // Set x5 = 0xFFFFFFFF, which is -1 as a signed number
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SUB), 5, 0, 1),
+            // x6 = 1 because 1 < 0xFFFFFFFF
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SLTU), 6, 1, 5),
+            // x6 = 0 because 0xFFFFFFFF < 1 doesn't hold
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SLTU), 6, 5, 1),
+            // Set x7 = 0xFFFFFFFE
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADD), 7, 5, 5),
+            // x6 = 1 because 0xFFFFFFFE < 0xFFFFFFFF
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SLTU), 6, 7, 5),
+            // x6 = 0 because 0xFFFFFFFF < 0xFFFFFFFF doesn't hold
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SLTU), 6, 5, 5),
         ]);
         vec![basic_block]
     }
@@ -182,6 +196,7 @@ mod test {
             CpuChip,
             DecodingCheckChip,
             AddChip,
+            SubChip,
             SltuChip,
             RegisterMemCheckChip,
             ProgramMemCheckChip,
@@ -369,6 +369,12 @@ mod test {
             Instruction::new_ir(Opcode::from(BuiltinOpcode::SRAI), 16, 15, 1),
             // x17 = x15 >> 31 using SRAI (should be 0)
             Instruction::new_ir(Opcode::from(BuiltinOpcode::SRAI), 17, 15, 31),
+            // x18 = x12 >> 15 using SRAI (-20 >> 15 = -1)
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SRAI), 18, 12, 15),
+            // x19 = x9 >> 15 using SRAI (should be 0xFFFF0000)
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SRAI), 19, 9, 15),
+            // x20 = x9 >> 31 using SRAI (should be 0xFFFFFFFF)
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SRAI), 20, 9, 31),
             // x9 = x17 >> 0
             Instruction::new_ir(Opcode::from(BuiltinOpcode::SRA), 9, 17, 0),
             Instruction::new_ir(Opcode::from(BuiltinOpcode::SRAI), 9, 17, 0),
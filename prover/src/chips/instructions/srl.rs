@@ -283,6 +283,8 @@ mod test {
             Instruction::new_ir(Opcode::from(BuiltinOpcode::SLLI), 7, 7, 31),
             // x8 = x7 >> 31 (0x80000000 >> 31 = 1)
             Instruction::new_ir(Opcode::from(BuiltinOpcode::SRLI), 8, 7, 31),
+            // x9 = x5 >> 15 (0xFFFFFFEC >> 15 = 0x1FFFF)
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SRLI), 9, 5, 15),
         ]);
         vec![basic_block]
     }
@@ -3,7 +3,7 @@ use num_traits::{One, Zero};
 
 use nexus_vm::WORD_SIZE;
 use stwo_prover::{
-    constraint_framework::{logup::LogupTraceGenerator, EvalAtRow, Relation, RelationEntry},
+    constraint_framework::{EvalAtRow, Relation, RelationEntry},
     core::{
         backend::simd::m31::{PackedBaseField, LOG_N_LANES},
         fields::m31::BaseField,
@@ -11,14 +11,14 @@ use stwo_prover::{
 };
 
 use crate::{
-    column::{Column, PreprocessedColumn, ProgramColumn},
+    column::{Column, ProgramColumn},
     components::AllLookupElements,
     trace::{
-        eval::{preprocessed_trace_eval, program_trace_eval, trace_eval, TraceEval},
+        eval::{program_trace_eval, trace_eval, TraceEval},
         program_trace::ProgramTraces,
         sidenote::SideNote,
         utils::FromBaseFields,
-        FinalizedTraces, PreprocessedTraces, ProgramStep, TracesBuilder,
+        FinalizedTraces, InteractionTraceBuilder, PreprocessedTraces, ProgramStep, TracesBuilder,
     },
     traits::MachineChip,
 };
@@ -114,7 +114,7 @@ impl MachineChip for ProgramMemCheckChip {
     /// * 1 / lookup_element.combine(tuple) is subtracted for each instruction
     /// where tuples contain (the address, the whole word of the instruction, final counter value).
     fn fill_interaction_trace(
-        logup_trace_gen: &mut LogupTraceGenerator,
+        logup_trace_gen: &mut InteractionTraceBuilder,
         original_traces: &FinalizedTraces,
         _preprocessed_trace: &PreprocessedTraces,
         program_trace: &ProgramTraces,
@@ -156,7 +156,7 @@ impl MachineChip for ProgramMemCheckChip {
         let lookup_elements: &ProgramCheckLookupElements = lookup_elements.as_ref();
         // Constrain the program counter on the first row
         let pc = trace_eval!(trace_eval, Column::Pc);
-        let [is_first] = preprocessed_trace_eval!(trace_eval, PreprocessedColumn::IsFirst);
+        let is_first = trace_eval.is_first_row();
         let initial_pc = program_trace_eval!(trace_eval, ProgramColumn::PrgInitialPc);
         for limb_idx in 0..WORD_SIZE {
             eval.add_constraint(
@@ -223,7 +223,7 @@ impl ProgramMemCheckChip {
     ///
     /// The initial content of the memory is located on rows where PrgMemoryFlag is 1.
     fn add_initial_digest(
-        logup_trace_gen: &mut LogupTraceGenerator,
+        logup_trace_gen: &mut InteractionTraceBuilder,
         original_traces: &FinalizedTraces,
         program_traces: &ProgramTraces,
         lookup_element: &ProgramCheckLookupElements,
@@ -304,7 +304,7 @@ impl ProgramMemCheckChip {
     /// Most columns are the same as the initial program memory content.
     /// The final counter is located on the FinalPrgMemoryCtr column.
     fn subtract_final_digest(
-        logup_trace_gen: &mut LogupTraceGenerator,
+        logup_trace_gen: &mut InteractionTraceBuilder,
         original_traces: &FinalizedTraces,
         program_traces: &ProgramTraces,
         lookup_element: &ProgramCheckLookupElements,
@@ -386,7 +386,7 @@ impl ProgramMemCheckChip {
     ///
     /// The numerator is zero on the padding rows, so that the row doesn't contribute to the logup sum.
     fn subtract_access(
-        logup_trace_gen: &mut LogupTraceGenerator,
+        logup_trace_gen: &mut InteractionTraceBuilder,
         original_traces: &FinalizedTraces,
         lookup_element: &ProgramCheckLookupElements,
     ) {
@@ -464,7 +464,7 @@ impl ProgramMemCheckChip {
     ///
     /// The numerator is zero when the row is padding, so that the row doesn't contribute to the logup sum.
     fn add_access(
-        logup_trace_gen: &mut LogupTraceGenerator,
+        logup_trace_gen: &mut InteractionTraceBuilder,
         original_traces: &FinalizedTraces,
         lookup_element: &ProgramCheckLookupElements,
     ) {
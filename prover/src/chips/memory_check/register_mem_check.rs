@@ -1,7 +1,7 @@
 use nexus_vm::WORD_SIZE;
 use num_traits::Zero;
 use stwo_prover::{
-    constraint_framework::{logup::LogupTraceGenerator, EvalAtRow, Relation, RelationEntry},
+    constraint_framework::{EvalAtRow, Relation, RelationEntry},
     core::{backend::simd::m31::LOG_N_LANES, fields::m31::BaseField},
 };
 
@@ -21,7 +21,7 @@ use crate::{
         regs::AccessResult,
         sidenote::SideNote,
         utils::FromBaseFields,
-        FinalizedTraces, PreprocessedTraces, ProgramStep, TracesBuilder,
+        FinalizedTraces, InteractionTraceBuilder, PreprocessedTraces, ProgramStep, TracesBuilder,
     },
     traits::MachineChip,
     virtual_column::{self, IsTypeR, OpBFlag, Reg3Accessed, VirtualColumn},
@@ -204,6 +204,7 @@ impl MachineChip for RegisterMemCheckChip {
         let value_c = trace_eval!(trace_eval, Column::ValueC);
 
         // is_alu = is_add + is_sub + is_slt + is_sltu + is_xor + is_or + is_and + is_sll + is_srl + is_sra
+        //     + is_mul + is_mulh + is_mulhsu + is_mulhu + is_div + is_divu + is_rem + is_remu
         let [is_alu] = virtual_column::IsAlu::eval(trace_eval);
 
         for i in 0..WORD_SIZE {
@@ -223,7 +224,7 @@ impl MachineChip for RegisterMemCheckChip {
     }
 
     fn fill_interaction_trace(
-        logup_trace_gen: &mut LogupTraceGenerator,
+        logup_trace_gen: &mut InteractionTraceBuilder,
         original_traces: &FinalizedTraces,
         preprocessed_trace: &PreprocessedTraces,
         _program_trace: &ProgramTraces,
@@ -292,7 +293,7 @@ impl MachineChip for RegisterMemCheckChip {
 
 impl RegisterMemCheckChip {
     fn subtract_prev_reg<AccessFlag: VirtualColumn<1>>(
-        logup_trace_gen: &mut LogupTraceGenerator,
+        logup_trace_gen: &mut InteractionTraceBuilder,
         original_traces: &FinalizedTraces,
         lookup_element: &RegisterCheckLookupElements,
         reg_address: Column,
@@ -343,7 +344,7 @@ impl RegisterMemCheckChip {
     }
 
     fn add_cur_reg<AccessFlag: VirtualColumn<1>>(
-        logup_trace_gen: &mut LogupTraceGenerator,
+        logup_trace_gen: &mut InteractionTraceBuilder,
         original_traces: &FinalizedTraces,
         preprocessed_trace: &PreprocessedTraces,
         lookup_element: &RegisterCheckLookupElements,
@@ -431,13 +432,11 @@ mod test {
         riscv::{BasicBlock, BuiltinOpcode, Instruction, Opcode},
         trace::k_trace_direct,
     };
-    use num_traits::Zero;
-    use stwo_prover::core::fields::qm31::SecureField;
 
     use crate::{
         chips::{AddChip, CpuChip},
         extensions::ExtensionComponent,
-        test_utils::assert_chip,
+        test_utils::assert_chip_with_logup,
         trace::{
             program::iter_program_steps, program_trace::ProgramTracesBuilder, PreprocessedTraces,
             TracesBuilder,
@@ -513,11 +512,11 @@ mod test {
                 &mut side_note,
             );
         }
-        let (lookup_elements, claimed_sum_1) = assert_chip::<RegisterMemCheckChip>(traces, None);
-
         // verify that logup sums match
-        let ext = ExtensionComponent::final_reg();
-        let (_, claimed_sum_2) = ext.generate_interaction_trace(&side_note, &lookup_elements);
-        assert_eq!(claimed_sum_1 + claimed_sum_2, SecureField::zero());
+        assert_chip_with_logup::<RegisterMemCheckChip>(traces, None, |lookup_elements| {
+            let ext = ExtensionComponent::final_reg();
+            ext.generate_interaction_trace(&side_note, lookup_elements)
+                .1
+        });
     }
 }
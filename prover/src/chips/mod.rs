@@ -6,8 +6,8 @@ pub(crate) mod range_check;
 
 pub use instructions::{
     add_with_carries, subtract_with_borrow, AddChip, AuipcChip, BeqChip, BgeChip, BgeuChip,
-    BitOpChip, BltChip, BltuChip, BneChip, JalChip, JalrChip, LoadStoreChip, LuiChip, SllChip,
-    SltChip, SltuChip, SraChip, SrlChip, SubChip, SyscallChip,
+    BitOpChip, BltChip, BltuChip, BneChip, JalChip, JalrChip, LoadStoreChip, LuiChip, MulDivChip,
+    SllChip, SltChip, SltuChip, SraChip, SrlChip, SubChip, SyscallChip,
 };
 
 pub use cpu::CpuChip;
@@ -1,6 +1,6 @@
 // This file contains range-checking values for 0..=255.
 
-use stwo_prover::constraint_framework::{logup::LogupTraceGenerator, Relation, RelationEntry};
+use stwo_prover::constraint_framework::{Relation, RelationEntry};
 
 use nexus_vm::WORD_SIZE;
 use num_traits::{One, Zero};
@@ -11,8 +11,9 @@ use stwo_prover::core::{
 
 use crate::{
     column::Column::{
-        self, CReg1TsPrev, CReg2TsPrev, CReg3TsPrev, FinalPrgMemoryCtr, Helper1, InstrVal,
-        OpC16_23, OpC24_31, Pc, PcNextAux, PrevCtr, ProgCtrCur, ProgCtrPrev, Qt, Ram1TsPrev,
+        self, AbsQt, AbsRem, AbsValueB, AbsValueC, CReg1TsPrev, CReg2TsPrev, CReg3TsPrev,
+        FinalPrgMemoryCtr, Helper1, InstrVal, MulCarry0, MulCarry1, MulCarry2, OpC16_23, OpC24_31,
+        Pc, PcNextAux, PrevCtr, ProdHigh, ProdLow, ProgCtrCur, ProgCtrPrev, Qt, Ram1TsPrev,
         Ram1TsPrevAux, Ram1ValCur, Ram1ValPrev, Ram2TsPrev, Ram2TsPrevAux, Ram2ValCur, Ram2ValPrev,
         Ram3TsPrev, Ram3TsPrevAux, Ram3ValCur, Ram3ValPrev, Ram4TsPrev, Ram4TsPrevAux, Ram4ValCur,
         Ram4ValPrev, RamBaseAddr, RamFinalCounter, RamFinalValue, RamInitFinalAddr, Reg1TsPrev,
@@ -21,7 +22,7 @@ use crate::{
     components::AllLookupElements,
     trace::{
         eval::TraceEval, program_trace::ProgramTraces, sidenote::SideNote, FinalizedTraces,
-        PreprocessedTraces, ProgramStep, TracesBuilder,
+        InteractionTraceBuilder, PreprocessedTraces, ProgramStep, TracesBuilder,
     },
     traits::MachineChip,
     virtual_column::{self, VirtualColumn},
@@ -36,7 +37,7 @@ const LOOKUP_TUPLE_SIZE: usize = 1;
 stwo_prover::relation!(Range256LookupElements, LOOKUP_TUPLE_SIZE);
 
 impl Range256Chip {
-    const CHECKED_WORDS: [Column; 31] = [
+    const CHECKED_WORDS: [Column; 40] = [
         Pc,
         PcNextAux,
         InstrVal,
@@ -68,6 +69,15 @@ impl Range256Chip {
         RemDiff,
         RamInitFinalAddr,
         RamFinalCounter,
+        ProdLow,
+        ProdHigh,
+        MulCarry0,
+        MulCarry1,
+        MulCarry2,
+        AbsValueB,
+        AbsValueC,
+        AbsQt,
+        AbsRem,
     ];
 
     const CHECKED_BYTES: [Column; 9] = [
@@ -127,7 +137,7 @@ impl MachineChip for Range256Chip {
     ///
     /// data[vec_row] contains sixteen rows. A single write_frac() adds sixteen numbers.
     fn fill_interaction_trace(
-        logup_trace_gen: &mut LogupTraceGenerator,
+        logup_trace_gen: &mut InteractionTraceBuilder,
         original_traces: &FinalizedTraces,
         _preprocessed_traces: &PreprocessedTraces,
         _program_traces: &ProgramTraces,
@@ -231,7 +241,7 @@ fn fill_main_cols<const N: usize>(value_col: [BaseField; N], side_note: &mut Sid
 fn check_bytes<const N: usize>(
     basecolumn: [&BaseColumn; N],
     log_size: u32,
-    logup_trace_gen: &mut LogupTraceGenerator,
+    logup_trace_gen: &mut InteractionTraceBuilder,
     lookup_element: &Range256LookupElements,
 ) {
     // TODO: we can deal with two limbs at a time.
@@ -1,6 +1,6 @@
 // This file contains range-checking values for 0..=31.
 
-use stwo_prover::constraint_framework::{logup::LogupTraceGenerator, Relation, RelationEntry};
+use stwo_prover::constraint_framework::RelationEntry;
 
 use num_traits::One;
 use stwo_prover::core::{
@@ -13,7 +13,7 @@ use crate::{
     components::AllLookupElements,
     trace::{
         eval::TraceEval, program_trace::ProgramTraces, sidenote::SideNote, FinalizedTraces,
-        PreprocessedTraces, ProgramStep, TracesBuilder,
+        InteractionTraceBuilder, PreprocessedTraces, ProgramStep, TracesBuilder,
     },
     traits::MachineChip,
 };
@@ -50,30 +50,30 @@ impl MachineChip for Range32Chip {
     }
     /// Fills the whole interaction trace in one-go using SIMD in the stwo-usual way
     ///
-    /// data[vec_row] contains sixteen rows. A single write_frac() adds sixteen numbers.
+    /// data[vec_row] contains sixteen rows. A single add_lookup() adds sixteen numbers.
     fn fill_interaction_trace(
-        logup_trace_gen: &mut LogupTraceGenerator,
+        interaction_trace: &mut InteractionTraceBuilder,
         original_traces: &FinalizedTraces,
         _preprocessed_traces: &PreprocessedTraces,
         _program_traces: &ProgramTraces,
         lookup_element: &AllLookupElements,
     ) {
         let lookup_element: &Range32LookupElements = lookup_element.as_ref();
+        let log_size = original_traces.log_size();
 
         // Add checked occurrences to logup sum.
         // TODO: range-check other byte-ranged columns.
-        for col in CHECKED.iter() {
-            let [value_basecolumn]: [_; 1] = original_traces.get_base_column(*col);
-            let log_size = original_traces.log_size();
-            // TODO: we can deal with two limbs at a time.
-            let mut logup_col_gen = logup_trace_gen.new_col();
-            // vec_row is row_idx divided by 16. Because SIMD.
-            for vec_row in 0..(1 << (log_size - LOG_N_LANES)) {
-                let checked_tuple = vec![value_basecolumn.data[vec_row]];
-                let denom = lookup_element.combine(&checked_tuple);
-                logup_col_gen.write_frac(vec_row, SecureField::one().into(), denom);
+        // vec_row is row_idx divided by 16. Because SIMD.
+        for vec_row in 0..(1 << (log_size - LOG_N_LANES)) {
+            for col in CHECKED.iter() {
+                let [value_basecolumn]: [_; 1] = original_traces.get_base_column(*col);
+                interaction_trace.add_lookup(
+                    vec_row,
+                    lookup_element,
+                    &[value_basecolumn.data[vec_row]],
+                    SecureField::one(),
+                );
             }
-            logup_col_gen.finalize_col();
         }
     }
 
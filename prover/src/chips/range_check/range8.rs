@@ -4,7 +4,7 @@ use nexus_vm::{
     riscv::{BuiltinOpcode, InstructionType},
     WORD_SIZE,
 };
-use stwo_prover::constraint_framework::{logup::LogupTraceGenerator, Relation, RelationEntry};
+use stwo_prover::constraint_framework::{Relation, RelationEntry};
 
 use num_traits::Zero;
 use stwo_prover::core::{
@@ -17,7 +17,7 @@ use crate::{
     components::AllLookupElements,
     trace::{
         eval::TraceEval, program_trace::ProgramTraces, sidenote::SideNote, FinalizedTraces,
-        PreprocessedTraces, ProgramStep, TracesBuilder,
+        InteractionTraceBuilder, PreprocessedTraces, ProgramStep, TracesBuilder,
     },
     traits::MachineChip,
     virtual_column::{
@@ -118,7 +118,7 @@ impl MachineChip for Range8Chip {
     ///
     /// data[vec_row] contains sixteen rows. A single write_frac() adds sixteen numbers.
     fn fill_interaction_trace(
-        logup_trace_gen: &mut LogupTraceGenerator,
+        logup_trace_gen: &mut InteractionTraceBuilder,
         original_traces: &FinalizedTraces,
         _preprocessed_traces: &PreprocessedTraces,
         _program_traces: &ProgramTraces,
@@ -220,13 +220,13 @@ fn add_constraints_for_type<
 fn fill_interaction_for_type<VC: VirtualColumn<1>>(
     original_traces: &FinalizedTraces,
     lookup_element: &Range8LookupElements,
-    logup_trace_gen: &mut LogupTraceGenerator,
+    logup_trace_gen: &mut InteractionTraceBuilder,
     cols: &[Column],
 ) {
     for col in cols.iter() {
         let [value_basecolumn]: [&BaseColumn; 1] = original_traces.get_base_column(*col);
         let log_size = original_traces.log_size();
-        let logup_trace_gen: &mut LogupTraceGenerator = logup_trace_gen;
+        let logup_trace_gen: &mut InteractionTraceBuilder = logup_trace_gen;
         // TODO: we can deal with two limbs at a time.
         let mut logup_col_gen = logup_trace_gen.new_col();
         // vec_row is row_idx divided by 16. Because SIMD.
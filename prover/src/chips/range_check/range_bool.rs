@@ -1,20 +1,19 @@
 // This file contains range-checking for columns containing only {0, 1}
 
-use num_traits::One;
-
 use crate::{
     column::Column::{
-        self, BorrowFlag, CH1Minus, CH2Minus, CH3Minus, CarryFlag, ImmC, IsAdd, IsAnd, IsAuipc,
-        IsBge, IsBgeu, IsBlt, IsBltu, IsEbreak, IsEcall, IsJal, IsJalr, IsLb, IsLbu, IsLh, IsLhu,
-        IsLui, IsLw, IsOr, IsPadding, IsSb, IsSh, IsSll, IsSlt, IsSltu, IsSra, IsSrl, IsSub, IsSw,
+        self, BorrowFlag, CH1Minus, CH2Minus, CH3Minus, CarryFlag, DivByZero, ImmC, IsAdd, IsAnd,
+        IsAuipc, IsBge, IsBgeu, IsBlt, IsBltu, IsDiv, IsDivu, IsEbreak, IsEcall, IsFence, IsJal,
+        IsJalr, IsLb, IsLbu, IsLh, IsLhu, IsLui, IsLw, IsMul, IsMulh, IsMulhsu, IsMulhu, IsOr,
+        IsPadding, IsRem, IsRemu, IsSb, IsSh, IsSll, IsSlt, IsSltu, IsSra, IsSrl, IsSub, IsSw,
         IsSysCycleCount, IsSysDebug, IsSysHalt, IsSysHeapReset, IsSysPrivInput, IsSysStackReset,
-        IsXor, LtFlag, OpA0, OpB0, OpB4, OpC0, OpC11, OpC12, OpC20, OpC4, PcCarry, ProgCtrCarry,
-        RamInitFinalFlag, RemAux, SgnA, SgnB, SgnC, ShiftBit1, ShiftBit2, ShiftBit3, ShiftBit4,
-        ShiftBit5, ValueAEffectiveFlag,
+        IsXor, LtFlag, MulhCarry, NegCarry, OpA0, OpB0, OpB4, OpC0, OpC11, OpC12, OpC20, OpC4,
+        PcCarry, ProgCtrCarry, RamInitFinalFlag, RemAux, SgnA, SgnB, SgnBXorC, SgnC, SgnQt, SgnRem,
+        ShiftBit1, ShiftBit2, ShiftBit3, ShiftBit4, ShiftBit5, ValueAEffectiveFlag,
     },
     components::AllLookupElements,
     trace::{eval::TraceEval, sidenote::SideNote, ProgramStep, TracesBuilder},
-    traits::MachineChip,
+    traits::{check_columns_bounded, MachineChip, WitnessError},
     virtual_column::{self, VirtualColumn},
 };
 
@@ -23,7 +22,7 @@ use crate::{
 /// RangeBoolChip can be located anywhere in the chip composition.
 pub struct RangeBoolChip;
 
-const CHECKED_SINGLE: [Column; 48] = [
+const CHECKED_SINGLE: [Column; 61] = [
     ValueAEffectiveFlag,
     ImmC,
     IsAdd,
@@ -52,8 +51,17 @@ const CHECKED_SINGLE: [Column; 48] = [
     IsSll,
     IsSrl,
     IsSra,
+    IsMul,
+    IsMulh,
+    IsMulhsu,
+    IsMulhu,
+    IsDiv,
+    IsDivu,
+    IsRem,
+    IsRemu,
     IsEcall,
     IsEbreak,
+    IsFence,
     IsSysCycleCount,
     IsSysDebug,
     IsSysHalt,
@@ -72,6 +80,10 @@ const CHECKED_SINGLE: [Column; 48] = [
     ShiftBit4,
     ShiftBit5,
     RamInitFinalFlag,
+    DivByZero,
+    SgnQt,
+    SgnRem,
+    SgnBXorC,
 ];
 const CHECKED_HALF_WORD: [Column; 7] = [
     CarryFlag,
@@ -82,6 +94,7 @@ const CHECKED_HALF_WORD: [Column; 7] = [
     ProgCtrCarry,
     BorrowFlag,
 ];
+const CHECKED_WORD: [Column; 2] = [MulhCarry, NegCarry];
 const TYPE_R_CHECKED_SINGLE: [Column; 3] = [OpC4, OpA0, OpB0];
 const TYPE_I_NO_SHIFT_SINGLE: [Column; 3] = [OpC11, OpA0, OpB0];
 const TYPE_I_SHIFT_SINGLE: [Column; 3] = [OpC4, OpA0, OpB0];
@@ -104,54 +117,53 @@ impl MachineChip for RangeBoolChip {
         trace_eval: &TraceEval<E>,
         _lookup_elements: &AllLookupElements,
     ) {
-        for col in CHECKED_SINGLE.into_iter() {
-            let [col] = trace_eval.column_eval(col);
-            eval.add_constraint(col.clone() * (col - E::F::one()));
-        }
-
-        for col in CHECKED_HALF_WORD.into_iter() {
-            let col_limbs = trace_eval.column_eval::<2>(col);
-            for limb in col_limbs.into_iter() {
-                eval.add_constraint(limb.clone() * (limb - E::F::one()));
-            }
+        for col in CHECKED_SINGLE
+            .into_iter()
+            .chain(CHECKED_HALF_WORD)
+            .chain(CHECKED_WORD)
+        {
+            trace_eval.assert_boolean(eval, col);
         }
 
         let [type_r] = virtual_column::IsTypeR::eval(trace_eval);
-        for col in TYPE_R_CHECKED_SINGLE.into_iter() {
-            let [col] = trace_eval.column_eval(col);
-            eval.add_constraint(type_r.clone() * col.clone() * (col - E::F::one()));
+        for col in TYPE_R_CHECKED_SINGLE {
+            trace_eval.assert_boolean_or_zero(eval, col, type_r.clone());
         }
 
         let [is_type_i_no_shift] = virtual_column::IsTypeINoShift::eval(trace_eval);
         for col in TYPE_I_NO_SHIFT_SINGLE {
-            let [col] = trace_eval.column_eval(col);
-            eval.add_constraint(is_type_i_no_shift.clone() * col.clone() * (col - E::F::one()));
+            trace_eval.assert_boolean_or_zero(eval, col, is_type_i_no_shift.clone());
         }
 
         let [is_alu_imm_shift] = virtual_column::IsAluImmShift::eval(trace_eval);
         for col in TYPE_I_SHIFT_SINGLE {
-            let [col] = trace_eval.column_eval(col);
-            eval.add_constraint(is_alu_imm_shift.clone() * col.clone() * (col - E::F::one()));
+            trace_eval.assert_boolean_or_zero(eval, col, is_alu_imm_shift.clone());
         }
 
         let [is_type_j] = virtual_column::IsTypeJ::eval(trace_eval);
         for col in TYPE_J_CHECKED_SINGLE {
-            let [col] = trace_eval.column_eval(col);
-            eval.add_constraint(is_type_j.clone() * col.clone() * (col - E::F::one()));
+            trace_eval.assert_boolean_or_zero(eval, col, is_type_j.clone());
         }
 
         let [is_type_b] = virtual_column::IsTypeB::eval(trace_eval);
         for col in TYPE_B_CHECKED_SINGLE {
-            let [col] = trace_eval.column_eval(col);
-            eval.add_constraint(is_type_b.clone() * col.clone() * (col - E::F::one()));
+            trace_eval.assert_boolean_or_zero(eval, col, is_type_b.clone());
         }
 
         let [is_type_s] = virtual_column::IsTypeS::eval(trace_eval);
         for col in TYPE_S_CHECKED_SINGLE {
-            let [col] = trace_eval.column_eval(col);
-            eval.add_constraint(is_type_s.clone() * col.clone() * (col - E::F::one()));
+            trace_eval.assert_boolean_or_zero(eval, col, is_type_s.clone());
         }
     }
+
+    fn validate_witness(traces: &TracesBuilder) -> Result<(), WitnessError> {
+        let cols: Vec<Column> = CHECKED_SINGLE
+            .into_iter()
+            .chain(CHECKED_HALF_WORD)
+            .chain(CHECKED_WORD)
+            .collect();
+        check_columns_bounded(traces, &cols, 1)
+    }
 }
 
 #[cfg(test)]
@@ -197,6 +209,20 @@ mod test {
         assert_chip::<RangeBoolChip>(traces, None);
     }
 
+    #[test]
+    #[should_panic(expected = "invalid witness: row 3: IsAdd[0] = 2 exceeds 1")]
+    fn test_range_bool_chip_non_boolean_flag() {
+        const LOG_SIZE: u32 = PreprocessedTraces::MIN_LOG_SIZE;
+        let mut traces = TracesBuilder::new(LOG_SIZE);
+        for row_idx in 0..traces.num_rows() {
+            for col in CHECKED_SINGLE.into_iter() {
+                traces.fill_columns(row_idx, row_idx % 2 == 0, col);
+            }
+        }
+        traces.fill_columns(3, 2u8, IsAdd);
+        assert_chip::<RangeBoolChip>(traces, None);
+    }
+
     #[test]
     #[should_panic]
     fn range_bool_chip_fail_out_of_range() {
@@ -10,6 +10,40 @@ const _: () = {
     assert!(WORD_SIZE == 4usize);
 };
 
+/// Implements `verify_column_layout()` for a [`ColumnsEnum`] and asserts it at compile time.
+///
+/// The layout is valid if every variant starts right where the previous one ends, the first one starts at zero
+/// and the last one ends at `COLUMNS_NUM`, so that raw columns of different variants never overlap.
+///
+/// `offset()`, `size()` and `COLUMNS_NUM` are all derived by [`ColumnsEnum`] from `#[size = N]` attributes. To add
+/// a column, add a variant with the size attribute anywhere in the enum: offsets of the following variants and
+/// `COLUMNS_NUM` are updated automatically, and this check fails to compile if they ever get out of sync.
+macro_rules! verify_column_layout {
+    ($col:ty) => {
+        impl $col {
+            const fn verify_column_layout() -> bool {
+                let variants = Self::ALL_VARIANTS;
+                let mut expected_offset = 0;
+                let mut i = 0;
+                while i < variants.len() {
+                    if variants[i].offset() != expected_offset || variants[i].size() == 0 {
+                        return false;
+                    }
+                    expected_offset += variants[i].size();
+                    i += 1;
+                }
+                expected_offset == Self::COLUMNS_NUM
+            }
+        }
+
+        const _: () = assert!(<$col>::verify_column_layout());
+    };
+}
+
+verify_column_layout!(Column);
+verify_column_layout!(ProgramColumn);
+verify_column_layout!(PreprocessedColumn);
+
 impl Column {
     /// Returns `true` if the column requires mask values at the offset [0, 1], or in other words,
     /// constraints require both values at the current **and** next row, e.g. for constraining next
@@ -154,12 +188,39 @@ pub enum Column {
     /// Boolean flag on whether the row is a SRA.
     #[size = 1]
     IsSra,
+    /// Boolean flag on whether the row is a MUL.
+    #[size = 1]
+    IsMul,
+    /// Boolean flag on whether the row is a MULH.
+    #[size = 1]
+    IsMulh,
+    /// Boolean flag on whether the row is a MULHSU.
+    #[size = 1]
+    IsMulhsu,
+    /// Boolean flag on whether the row is a MULHU.
+    #[size = 1]
+    IsMulhu,
+    /// Boolean flag on whether the row is a DIV.
+    #[size = 1]
+    IsDiv,
+    /// Boolean flag on whether the row is a DIVU.
+    #[size = 1]
+    IsDivu,
+    /// Boolean flag on whether the row is a REM.
+    #[size = 1]
+    IsRem,
+    /// Boolean flag on whether the row is a REMU.
+    #[size = 1]
+    IsRemu,
     /// Boolean flag on whether the row is an ECALL.
     #[size = 1]
     IsEcall,
     /// Boolean flag on whether the row is an EBREAK.
     #[size = 1]
     IsEbreak,
+    /// Boolean flag on whether the row is a FENCE.
+    #[size = 1]
+    IsFence,
     /// Boolean flag on whether the row is an ECALL_DEBUG (Write).
     #[size = 1]
     IsSysDebug,
@@ -268,6 +329,57 @@ pub enum Column {
     #[size = 1]
     SraDegreeAux,
 
+    /// Lower word of the 64-bit product computed by the MUL/DIV chip.
+    #[size = 4]
+    ProdLow,
+    /// Upper word of the 64-bit product computed by the MUL/DIV chip.
+    #[size = 4]
+    ProdHigh,
+    /// Carries of the byte-wise product for byte positions 0 and 1, each carry is split into two bytes.
+    #[size = 4]
+    MulCarry0,
+    /// Carries of the byte-wise product for byte positions 2 and 3, each carry is split into two bytes.
+    #[size = 4]
+    MulCarry1,
+    /// Carries of the byte-wise product for byte positions 4 and 5, each carry is split into two bytes.
+    #[size = 4]
+    MulCarry2,
+    /// 1 indicates the divisor of DIV/DIVU/REM/REMU is zero.
+    #[size = 1]
+    DivByZero,
+    /// Inverse of the sum of divisor bytes, when it's non-zero.
+    #[size = 1]
+    DivisorSumInv,
+    /// Carries of the half-word addition `result + sgn_b・value_c + sgn_c・value_b = prod_high (mod 2^32)` of
+    /// MULH/MULHSU. Each of the two carries is in 0..=2 and is split into two bits.
+    #[size = 4]
+    MulhCarry,
+    /// Absolute value of the dividend of DIV/REM, equals ValueB for DIVU/REMU.
+    #[size = 4]
+    AbsValueB,
+    /// Absolute value of the divisor of DIV/REM, equals ValueC for DIVU/REMU.
+    #[size = 4]
+    AbsValueC,
+    /// Absolute value of the quotient of DIV/REM, equals Qt for DIVU/REMU.
+    #[size = 4]
+    AbsQt,
+    /// Absolute value of the remainder of DIV/REM, equals Rem for DIVU/REMU.
+    #[size = 4]
+    AbsRem,
+    /// Sign bit of the quotient of DIV/REM.
+    #[size = 1]
+    SgnQt,
+    /// Sign bit of the remainder of DIV/REM.
+    #[size = 1]
+    SgnRem,
+    /// `sgn_b xor sgn_c` of DIV/REM, the sign of a non-zero quotient.
+    #[size = 1]
+    SgnBXorC,
+    /// Carries out of the lower half-word of the negations `abs + value = 2^32` of the dividend, divisor, quotient
+    /// and remainder of DIV/REM, in this order.
+    #[size = 4]
+    NegCarry,
+
     /// 1 indicates OpA is non-zero, 0 indicates OpA is zero
     #[size = 1]
     ValueAEffectiveFlag,
@@ -0,0 +1,86 @@
+//! Size of the constraint system of a set of chips, for comparing layouts before proving.
+//!
+//! See [`constraint_system_stats`].
+
+use crate::{
+    column::{PreprocessedColumn, ProgramColumn},
+    components::{machine_component_info, LOG_CONSTRAINT_DEGREE},
+    trace::eval::{INTERACTION_TRACE_IDX, ORIGINAL_TRACE_IDX},
+    traits::MachineChip,
+};
+
+/// Column and cell counts of the AIR of a set of chips, see [`constraint_system_stats`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConstraintStats {
+    /// Number of preprocessed and program columns.
+    pub preprocessed_column_count: usize,
+    /// Number of main trace columns.
+    pub column_count: usize,
+    /// Number of interaction trace columns, used by logup.
+    pub interaction_column_count: usize,
+    /// Number of cells in all committed traces.
+    pub total_cells: u64,
+    /// Relative cost of interpolating and evaluating all columns on the composition domain, in field operations.
+    ///
+    /// Only meaningful for comparisons between chip sets or trace sizes.
+    pub estimated_prover_work: f64,
+}
+
+/// Returns the size of the constraint system of chips `C` for a trace with `2.pow(log_size)` rows.
+///
+/// Extensions are not included.
+///
+/// ```ignore
+/// let stats = constraint_system_stats::<BaseComponent>(16);
+/// println!("{} columns, {} cells", stats.column_count, stats.total_cells);
+/// ```
+pub fn constraint_system_stats<C: MachineChip>(log_size: u32) -> ConstraintStats {
+    let info = machine_component_info::<C>();
+    let preprocessed_column_count = PreprocessedColumn::COLUMNS_NUM + ProgramColumn::COLUMNS_NUM;
+    let column_count = info.mask_offsets[ORIGINAL_TRACE_IDX].len();
+    let interaction_column_count = info.mask_offsets[INTERACTION_TRACE_IDX].len();
+
+    let n_columns = preprocessed_column_count + column_count + interaction_column_count;
+    let total_cells = (n_columns as u64) << log_size;
+    // Every column is interpolated and evaluated on the blown-up domain, each costing `n log n` operations.
+    let log_domain_size = log_size + LOG_CONSTRAINT_DEGREE;
+    let estimated_prover_work =
+        n_columns as f64 * (1u64 << log_domain_size) as f64 * log_domain_size as f64;
+
+    ConstraintStats {
+        preprocessed_column_count,
+        column_count,
+        interaction_column_count,
+        total_cells,
+        estimated_prover_work,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chips::{AddChip, CpuChip};
+    use crate::machine::BaseComponent;
+
+    #[test]
+    fn stats_grow_with_chips_and_rows() {
+        let base = constraint_system_stats::<BaseComponent>(10);
+        assert!(base.column_count > 0);
+        assert!(base.interaction_column_count > 0);
+        assert_eq!(
+            base.total_cells,
+            ((base.preprocessed_column_count + base.column_count + base.interaction_column_count)
+                as u64)
+                << 10
+        );
+
+        let larger = constraint_system_stats::<BaseComponent>(11);
+        assert_eq!(larger.column_count, base.column_count);
+        assert_eq!(larger.total_cells, 2 * base.total_cells);
+        assert!(larger.estimated_prover_work > 2.0 * base.estimated_prover_work);
+
+        let small = constraint_system_stats::<(CpuChip, AddChip)>(10);
+        assert_eq!(small.column_count, base.column_count);
+        assert!(small.interaction_column_count < base.interaction_column_count);
+    }
+}
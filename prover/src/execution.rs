@@ -0,0 +1,66 @@
+//! Proving program executions without assembling the proving pipeline manually.
+
+use std::fmt;
+
+use stwo_prover::core::pcs::PcsConfig;
+
+use nexus_vm::{elf::ElfFile, error::VMError, trace::k_trace};
+
+use crate::{
+    machine::{BaseComponent, Machine},
+    verifier::PublicInputs,
+    Proof, ProvingError,
+};
+
+/// Configuration of [`prove_execution`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProverConfig {
+    /// Commitment scheme configuration, a proof must be verified with the same one.
+    pub pcs_config: PcsConfig,
+}
+
+#[derive(Debug)]
+pub enum ExecutionError {
+    /// The program failed to execute.
+    VM(VMError),
+    /// The execution couldn't be proven.
+    Proving(ProvingError),
+}
+
+impl fmt::Display for ExecutionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::VM(err) => write!(f, "execution failed: {err}"),
+            Self::Proving(err) => write!(f, "proving failed: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ExecutionError {}
+
+impl From<VMError> for ExecutionError {
+    fn from(err: VMError) -> Self {
+        Self::VM(err)
+    }
+}
+
+impl From<ProvingError> for ExecutionError {
+    fn from(err: ProvingError) -> Self {
+        Self::Proving(err)
+    }
+}
+
+/// Executes `elf` on the given inputs and proves the execution.
+///
+/// Returns the proof together with the public values it must be verified against, see
+/// [`verify_execution`](crate::verifier::verify_execution).
+pub fn prove_execution(
+    elf: &ElfFile,
+    public_input: &[u8],
+    private_input: &[u8],
+    config: ProverConfig,
+) -> Result<(Proof, PublicInputs), ExecutionError> {
+    let (view, trace) = k_trace(elf.clone(), &[], public_input, private_input, 1)?;
+    let proof = Machine::<BaseComponent>::prove_with_config(&[], &trace, &view, config.pcs_config)?;
+    Ok((proof, PublicInputs::from(&view)))
+}
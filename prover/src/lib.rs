@@ -1,3 +1,5 @@
+#![feature(portable_simd)]
+
 pub mod chips;
 pub mod components;
 pub mod extensions;
@@ -7,7 +9,13 @@ pub mod column;
 pub mod traits;
 pub mod virtual_column;
 
+pub mod constraint_stats;
+pub mod execution;
 pub mod machine;
+pub mod pcs_config;
+pub mod proof_size;
+pub mod serialization;
+pub mod verifier;
 
 #[cfg(test)]
 mod test_utils;
@@ -15,7 +23,14 @@ mod test_utils;
 use nexus_vm::emulator::InternalView;
 pub(crate) use nexus_vm::WORD_SIZE;
 
+pub use constraint_stats::{constraint_system_stats, ConstraintStats};
+pub use execution::{prove_execution, ExecutionError, ProverConfig};
 pub use machine::Proof;
+pub use pcs_config::{ConfigError, PcsConfigBuilder};
+pub use serialization::{deserialize_proof, serialize_proof, ProofDeserializeError};
+pub use verifier::{
+    verify_execution, verify_execution_proof, PublicInputs, VerifierConfig, VerifyError,
+};
 
 pub use stwo_prover::core::prover::{ProvingError, VerificationError};
 
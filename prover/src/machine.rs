@@ -30,13 +30,14 @@ use super::traits::MachineChip;
 use crate::{
     chips::{
         AddChip, AuipcChip, BeqChip, BgeChip, BgeuChip, BitOpChip, BltChip, BltuChip, BneChip,
-        CpuChip, DecodingCheckChip, JalChip, JalrChip, LoadStoreChip, LuiChip, ProgramMemCheckChip,
-        RangeCheckChip, RegisterMemCheckChip, SllChip, SltChip, SltuChip, SraChip, SrlChip,
-        SubChip, SyscallChip, TimestampChip,
+        CpuChip, DecodingCheckChip, JalChip, JalrChip, LoadStoreChip, LuiChip, MulDivChip,
+        ProgramMemCheckChip, RangeCheckChip, RegisterMemCheckChip, SllChip, SltChip, SltuChip,
+        SraChip, SrlChip, SubChip, SyscallChip, TimestampChip,
     },
     column::{PreprocessedColumn, ProgramColumn},
     components::{self, AllLookupElements},
     extensions::ExtensionComponent,
+    proof_size::{estimate_proof_size_bytes, ProofShape},
     traits::generate_interaction_trace,
 };
 use serde::{Deserialize, Serialize};
@@ -62,6 +63,7 @@ pub type BaseComponent = (
     SllChip,
     SrlChip,
     SraChip,
+    MulDivChip,
     LoadStoreChip,
     SyscallChip,
     ProgramMemCheckChip,
@@ -82,6 +84,10 @@ const BASE_EXTENSIONS: &[ExtensionComponent] = &[
     ExtensionComponent::multiplicity256(),
 ];
 
+/// Prefix of the verification error returned when public inputs don't match the preprocessed trace commitment.
+pub(crate) const PREPROCESSED_COMMITMENT_MISMATCH: &str =
+    "invalid commitment to preprocessed trace";
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Proof {
     pub stark_proof: StarkProof<Blake2sMerkleHasher>,
@@ -124,16 +130,22 @@ impl<C: MachineChip + Sync> Machine<C> {
         trace: &impl Trace,
         view: &View,
     ) -> Result<Proof, ProvingError> {
-        let num_steps = trace.get_num_steps();
-        let program_len = view.get_program_memory().program.len();
-        let tracked_ram_size = view.view_tracked_ram_size();
+        Self::prove_with_config(extensions, trace, view, PcsConfig::default())
+    }
 
-        let log_size = Self::max_log_size(&[num_steps, program_len, tracked_ram_size])
-            .max(PreprocessedTraces::MIN_LOG_SIZE);
+    /// Same as [`Self::prove_with_extensions`] with a custom commitment scheme configuration.
+    ///
+    /// The proof can only be verified with the same `config`.
+    pub fn prove_with_config(
+        extensions: &[ExtensionComponent],
+        trace: &impl Trace,
+        view: &View,
+        config: PcsConfig,
+    ) -> Result<Proof, ProvingError> {
+        let log_size = trace_log_size(trace.get_num_steps(), view);
 
         let extensions_iter = BASE_EXTENSIONS.iter().chain(extensions);
 
-        let config = PcsConfig::default();
         // Precompute twiddles.
         let twiddles = SimdBackend::precompute_twiddles(
             CanonicCoset::new(
@@ -278,6 +290,29 @@ impl<C: MachineChip + Sync> Machine<C> {
         init_memory: &[MemoryInitializationEntry],
         exit_code: &[PublicOutputEntry],
         output_memory: &[PublicOutputEntry],
+    ) -> Result<(), VerificationError> {
+        Self::verify_with_config(
+            extensions,
+            proof,
+            program_info,
+            ad,
+            init_memory,
+            exit_code,
+            output_memory,
+            PcsConfig::default(),
+        )
+    }
+
+    /// Same as [`Self::verify_with_extensions`] for proofs generated with [`Self::prove_with_config`].
+    pub fn verify_with_config(
+        extensions: &[ExtensionComponent],
+        proof: Proof,
+        program_info: &ProgramInfo,
+        ad: &[u8],
+        init_memory: &[MemoryInitializationEntry],
+        exit_code: &[PublicOutputEntry],
+        output_memory: &[PublicOutputEntry],
+        config: PcsConfig,
     ) -> Result<(), VerificationError> {
         let Proof {
             stark_proof: proof,
@@ -297,7 +332,6 @@ impl<C: MachineChip + Sync> Machine<C> {
         }
         let extensions_iter = BASE_EXTENSIONS.iter().chain(extensions);
 
-        let config = PcsConfig::default();
         let verifier_channel = &mut Blake2sChannel::default();
         for &byte in ad {
             verifier_channel.mix_u64(byte.into());
@@ -307,7 +341,6 @@ impl<C: MachineChip + Sync> Machine<C> {
 
         // simulate the prover and compute expected commitment to preprocessed trace
         {
-            let config = PcsConfig::default();
             let verifier_channel = &mut verifier_channel.clone();
             let twiddles = SimdBackend::precompute_twiddles(
                 CanonicCoset::new(
@@ -346,7 +379,7 @@ impl<C: MachineChip + Sync> Machine<C> {
             let preprocessed_expected = commitment_scheme.roots()[PREPROCESSED_TRACE_IDX];
             let preprocessed = proof.commitments[PREPROCESSED_TRACE_IDX];
             if preprocessed_expected != preprocessed {
-                return Err(VerificationError::InvalidStructure(format!("invalid commitment to preprocessed trace: \
+                return Err(VerificationError::InvalidStructure(format!("{PREPROCESSED_COMMITMENT_MISMATCH}: \
                                                                         expected {preprocessed_expected}, got {preprocessed}")));
             }
         }
@@ -405,14 +438,66 @@ impl<C: MachineChip + Sync> Machine<C> {
         verify(&components_ref, verifier_channel, commitment_scheme, proof)
     }
 
-    /// Computes minimum allowed log_size from a slice of lengths.
-    fn max_log_size(sizes: &[usize]) -> u32 {
-        sizes
-            .iter()
-            .map(|size| size.next_power_of_two().trailing_zeros())
-            .max()
-            .expect("sizes is empty")
+    /// Returns the number of committed columns and sampled values of proofs generated with `extensions`.
+    pub fn proof_shape(extensions: &[ExtensionComponent]) -> ProofShape {
+        let mask_offsets = components::machine_component_info::<C>().mask_offsets;
+        let mut n_columns = [
+            PreprocessedColumn::COLUMNS_NUM + ProgramColumn::COLUMNS_NUM,
+            mask_offsets[ORIGINAL_TRACE_IDX].len(),
+            mask_offsets[INTERACTION_TRACE_IDX].len(),
+        ];
+        // preprocessed columns are only allowed to have [0] mask
+        let mut n_sampled_values = n_columns[PREPROCESSED_TRACE_IDX]
+            + [ORIGINAL_TRACE_IDX, INTERACTION_TRACE_IDX]
+                .iter()
+                .flat_map(|&idx| &mask_offsets[idx])
+                .map(Vec::len)
+                .sum::<usize>();
+
+        for ext in BASE_EXTENSIONS.iter().chain(extensions) {
+            let trace_sizes = ext.trace_sizes();
+            let n_ext_columns = [
+                ext.preprocessed_trace_sizes().len(),
+                trace_sizes.get(ORIGINAL_TRACE_IDX).map_or(0, Vec::len),
+                trace_sizes.get(INTERACTION_TRACE_IDX).map_or(0, Vec::len),
+            ];
+            for (n, n_ext) in n_columns.iter_mut().zip(n_ext_columns) {
+                *n += n_ext;
+            }
+            n_sampled_values += n_ext_columns.iter().sum::<usize>();
+        }
+
+        ProofShape {
+            n_columns,
+            n_sampled_values,
+            n_claimed_sums: 1 + BASE_EXTENSIONS.len() + extensions.len(),
+        }
     }
+
+    /// Estimates the size in bytes of a serialized proof, without generating it.
+    ///
+    /// The estimate is meant for choosing the configuration before an expensive proving run, the size of an
+    /// actual proof is returned by [`Proof::size_estimate`].
+    pub fn estimate_proof_size(
+        extensions: &[ExtensionComponent],
+        log_size: u32,
+        config: &PcsConfig,
+    ) -> usize {
+        estimate_proof_size_bytes(log_size, config, &Self::proof_shape(extensions))
+    }
+}
+
+/// Computes the log size of the traces that fit `num_steps` program steps, the program and the tracked RAM of `view`.
+pub(crate) fn trace_log_size(num_steps: usize, view: &View) -> u32 {
+    let program_len = view.get_program_memory().program.len();
+    let tracked_ram_size = view.view_tracked_ram_size();
+
+    [num_steps, program_len, tracked_ram_size]
+        .iter()
+        .map(|size| size.next_power_of_two().trailing_zeros())
+        .max()
+        .expect("sizes is empty")
+        .max(PreprocessedTraces::MIN_LOG_SIZE)
 }
 
 #[cfg(test)]
@@ -422,6 +507,7 @@ mod tests {
         riscv::{BasicBlock, BuiltinOpcode, Instruction, Opcode},
         trace::k_trace_direct,
     };
+    use stwo_prover::core::fri::FriConfig;
 
     #[test]
     fn prove_verify() {
@@ -447,4 +533,35 @@ mod tests {
         )
         .unwrap();
     }
+
+    #[test]
+    fn proof_size_estimate_is_calibrated() {
+        let basic_block = vec![BasicBlock::new(vec![
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 1, 0, 1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADD), 2, 1, 0),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADD), 3, 2, 1),
+        ])];
+        let (view, program_trace) =
+            k_trace_direct(&basic_block, 1).expect("error generating trace");
+
+        for (log_blowup_factor, n_queries) in [(1, 3), (1, 20), (2, 10)] {
+            let config = PcsConfig {
+                fri_config: FriConfig::new(0, log_blowup_factor, n_queries),
+                ..Default::default()
+            };
+            let proof =
+                Machine::<BaseComponent>::prove_with_config(&[], &program_trace, &view, config)
+                    .unwrap();
+            let actual = postcard::to_allocvec(&proof).unwrap().len();
+            let estimate =
+                Machine::<BaseComponent>::estimate_proof_size(&[], proof.log_size, &config);
+
+            let error = estimate.abs_diff(actual) as f64 / actual as f64;
+            assert!(
+                error <= 0.2,
+                "estimate {estimate} is off the actual size {actual} by {:.0}% (blowup 2^{log_blowup_factor}, {n_queries} queries)",
+                error * 100.0
+            );
+        }
+    }
 }
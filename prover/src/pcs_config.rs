@@ -0,0 +1,243 @@
+//! Choosing commitment scheme parameters for a target security level.
+//!
+//! A [`PcsConfig`] consists of:
+//!
+//! - `pow_bits`: the proof of work the prover grinds before queries are drawn. Adds `pow_bits` bits of security at
+//!   a cost of `2^pow_bits` hashes for the prover, doesn't affect the proof size.
+//! - `fri_config.log_blowup_factor`: traces are committed over a domain `2^log_blowup_factor` times larger than the
+//!   trace. Every query adds `log_blowup_factor` bits of security, but prover time and memory grow linearly with the
+//!   blowup factor.
+//! - `fri_config.n_queries`: number of opened positions. The proof size is roughly linear in the number of queries,
+//!   see [`estimate_proof_size_bytes`](crate::proof_size::estimate_proof_size_bytes).
+//!
+//! The resulting security is `pow_bits + log_blowup_factor * n_queries` bits, see [`PcsConfig::security_bits`].
+
+use std::{fmt, ops::RangeInclusive};
+
+use stwo_prover::core::{fri::FriConfig, pcs::PcsConfig};
+
+use crate::components::LOG_CONSTRAINT_DEGREE;
+
+/// Largest log size of a domain the composition polynomial can be evaluated on.
+const MAX_LOG_DOMAIN_SIZE: u32 = 30;
+
+const LOG_BLOWUP_FACTOR_RANGE: RangeInclusive<u32> = 1..=16;
+const LOG_LAST_LAYER_DEGREE_BOUND_RANGE: RangeInclusive<u32> = 0..=10;
+
+const DEFAULT_SECURITY_BITS: u32 = 96;
+const DEFAULT_POW_BITS: u32 = 10;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    /// The blowup factor is outside of the range supported by FRI.
+    BlowupFactorOutOfRange(u32),
+    /// The last layer degree bound is outside of the range supported by FRI.
+    LastLayerDegreeBoundOutOfRange(u32),
+    /// The blown-up composition domain of the trace is too large for the circle group of M31.
+    DomainTooLarge {
+        log_trace_size: u32,
+        log_blowup_factor: u32,
+    },
+    /// Proof of work alone can't reach the requested security level, at least one query is always required.
+    NoQueries,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BlowupFactorOutOfRange(log_blowup_factor) => write!(
+                f,
+                "log blowup factor {log_blowup_factor} is outside of {LOG_BLOWUP_FACTOR_RANGE:?}"
+            ),
+            Self::LastLayerDegreeBoundOutOfRange(bound) => write!(
+                f,
+                "log last layer degree bound {bound} is outside of {LOG_LAST_LAYER_DEGREE_BOUND_RANGE:?}"
+            ),
+            Self::DomainTooLarge {
+                log_trace_size,
+                log_blowup_factor,
+            } => write!(
+                f,
+                "trace of log size {log_trace_size} with log blowup factor {log_blowup_factor} exceeds the \
+                 largest evaluation domain of log size {MAX_LOG_DOMAIN_SIZE}"
+            ),
+            Self::NoQueries => write!(f, "the configuration must have at least one query"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Builds a [`PcsConfig`] with approximately the requested security level, with the number of queries derived from
+/// the other parameters.
+///
+/// ```ignore
+/// let config = PcsConfigBuilder::new()
+///     .with_security_bits(100)
+///     .for_trace_log_size(20)
+///     .build()?;
+/// assert!(config.security_bits() >= 100);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct PcsConfigBuilder {
+    security_bits: u32,
+    pow_bits: u32,
+    log_blowup_factor: u32,
+    log_last_layer_degree_bound: u32,
+    log_trace_size: Option<u32>,
+}
+
+impl Default for PcsConfigBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PcsConfigBuilder {
+    /// Returns a builder targeting 96 bits of security with 10 bits of proof of work and the smallest blowup factor.
+    pub fn new() -> Self {
+        Self {
+            security_bits: DEFAULT_SECURITY_BITS,
+            pow_bits: DEFAULT_POW_BITS,
+            log_blowup_factor: *LOG_BLOWUP_FACTOR_RANGE.start(),
+            log_last_layer_degree_bound: 0,
+            log_trace_size: None,
+        }
+    }
+
+    /// Targets `bits` bits of security, proof of work is capped by the target.
+    pub fn with_security_bits(mut self, bits: u32) -> Self {
+        self.security_bits = bits;
+        self
+    }
+
+    pub fn with_pow_bits(mut self, pow_bits: u32) -> Self {
+        self.pow_bits = pow_bits;
+        self
+    }
+
+    pub fn with_log_blowup_factor(mut self, log_blowup_factor: u32) -> Self {
+        self.log_blowup_factor = log_blowup_factor;
+        self
+    }
+
+    pub fn with_log_last_layer_degree_bound(mut self, log_last_layer_degree_bound: u32) -> Self {
+        self.log_last_layer_degree_bound = log_last_layer_degree_bound;
+        self
+    }
+
+    /// Picks the blowup factor for a trace of `2.pow(log_size)` rows.
+    ///
+    /// Small traces are cheap to blow up, a larger blowup factor needs fewer queries and results in a smaller proof.
+    /// Large traces use the smallest blowup factor to keep prover time and memory down.
+    pub fn for_trace_log_size(mut self, log_size: u32) -> Self {
+        self.log_trace_size = Some(log_size);
+        self.log_blowup_factor = match log_size {
+            ..=16 => 3,
+            17..=20 => 2,
+            _ => 1,
+        };
+        self
+    }
+
+    /// Validates parameters and returns the config with the least number of queries reaching the target security.
+    pub fn build(self) -> Result<PcsConfig, ConfigError> {
+        if !LOG_BLOWUP_FACTOR_RANGE.contains(&self.log_blowup_factor) {
+            return Err(ConfigError::BlowupFactorOutOfRange(self.log_blowup_factor));
+        }
+        if !LOG_LAST_LAYER_DEGREE_BOUND_RANGE.contains(&self.log_last_layer_degree_bound) {
+            return Err(ConfigError::LastLayerDegreeBoundOutOfRange(
+                self.log_last_layer_degree_bound,
+            ));
+        }
+        if let Some(log_trace_size) = self.log_trace_size {
+            if log_trace_size + LOG_CONSTRAINT_DEGREE + self.log_blowup_factor > MAX_LOG_DOMAIN_SIZE
+            {
+                return Err(ConfigError::DomainTooLarge {
+                    log_trace_size,
+                    log_blowup_factor: self.log_blowup_factor,
+                });
+            }
+        }
+        let pow_bits = self.pow_bits.min(self.security_bits);
+        let n_queries = (self.security_bits - pow_bits).div_ceil(self.log_blowup_factor) as usize;
+        if n_queries == 0 {
+            return Err(ConfigError::NoQueries);
+        }
+
+        Ok(PcsConfig {
+            pow_bits,
+            fri_config: FriConfig::new(
+                self.log_last_layer_degree_bound,
+                self.log_blowup_factor,
+                n_queries,
+            ),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_config_with_requested_security() {
+        for bits in [20, 96, 100, 128] {
+            for log_size in [8, 18, 24] {
+                let config = PcsConfigBuilder::new()
+                    .with_security_bits(bits)
+                    .for_trace_log_size(log_size)
+                    .build()
+                    .unwrap();
+                let security_bits = config.security_bits();
+                assert!(security_bits >= bits);
+                assert!(security_bits < bits + config.fri_config.log_blowup_factor);
+            }
+        }
+
+        let small = PcsConfigBuilder::new()
+            .for_trace_log_size(10)
+            .build()
+            .unwrap();
+        let large = PcsConfigBuilder::new()
+            .for_trace_log_size(24)
+            .build()
+            .unwrap();
+        assert!(small.fri_config.n_queries < large.fri_config.n_queries);
+    }
+
+    #[test]
+    fn rejects_invalid_parameters() {
+        assert_eq!(
+            PcsConfigBuilder::new()
+                .with_log_blowup_factor(0)
+                .build()
+                .unwrap_err(),
+            ConfigError::BlowupFactorOutOfRange(0)
+        );
+        assert_eq!(
+            PcsConfigBuilder::new()
+                .with_log_last_layer_degree_bound(11)
+                .build()
+                .unwrap_err(),
+            ConfigError::LastLayerDegreeBoundOutOfRange(11)
+        );
+        assert_eq!(
+            PcsConfigBuilder::new()
+                .for_trace_log_size(28)
+                .build()
+                .unwrap_err(),
+            ConfigError::DomainTooLarge {
+                log_trace_size: 28,
+                log_blowup_factor: 1
+            }
+        );
+        assert_eq!(
+            PcsConfigBuilder::new()
+                .with_security_bits(10)
+                .build()
+                .unwrap_err(),
+            ConfigError::NoQueries
+        );
+    }
+}
@@ -0,0 +1,93 @@
+//! Estimation of the serialized proof size without generating a proof.
+//!
+//! See [`Machine::estimate_proof_size`](crate::machine::Machine::estimate_proof_size).
+
+use stwo_prover::core::{fields::secure_column::SECURE_EXTENSION_DEGREE, pcs::PcsConfig};
+
+use crate::components::LOG_CONSTRAINT_DEGREE;
+
+/// Number of committed columns and out-of-domain samples of a proof, see [`estimate_proof_size_bytes`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProofShape {
+    /// Number of columns in the preprocessed, main, and interaction trees.
+    pub n_columns: [usize; 3],
+    /// Number of sampled values of all trace columns, one per column mask offset.
+    pub n_sampled_values: usize,
+    pub n_claimed_sums: usize,
+}
+
+// Sizes of proof elements encoded with postcard. Base field elements are varint-encoded, evaluations over the
+// blown-up domain are almost never small enough to take less than 5 bytes.
+const HASH_SIZE: usize = 32;
+const BASE_FIELD_SIZE: usize = 5;
+const SECURE_FIELD_SIZE: usize = SECURE_EXTENSION_DEGREE * BASE_FIELD_SIZE;
+
+/// Estimates the size of a serialized proof of a trace with `log_size` rows.
+pub fn estimate_proof_size_bytes(log_size: u32, config: &PcsConfig, shape: &ProofShape) -> usize {
+    let n_queries = config.fri_config.n_queries;
+    let log_blowup_factor = config.fri_config.log_blowup_factor;
+    // Top layers of Merkle trees are shared by authentication paths of all queries.
+    let log_n_queries = n_queries.next_power_of_two().trailing_zeros();
+    let merkle_paths_size = |log_domain_size: u32| {
+        n_queries * log_domain_size.saturating_sub(log_n_queries) as usize * HASH_SIZE
+    };
+
+    // Trace trees and the composition polynomial tree are opened on every query.
+    let log_composition_size = log_size + LOG_CONSTRAINT_DEGREE;
+    let trees_size: usize = shape
+        .n_columns
+        .iter()
+        .map(|&n_columns| (n_columns, log_size))
+        .chain([(SECURE_EXTENSION_DEGREE, log_composition_size)])
+        .map(|(n_columns, log_size)| {
+            HASH_SIZE
+                + n_columns * n_queries * BASE_FIELD_SIZE
+                + merkle_paths_size(log_size + log_blowup_factor)
+        })
+        .sum();
+    let sampled_values_size =
+        (shape.n_sampled_values + SECURE_EXTENSION_DEGREE) * SECURE_FIELD_SIZE;
+
+    // Every FRI layer is committed and opened with a sibling value per query.
+    let log_last_layer_degree_bound = config.fri_config.log_last_layer_degree_bound;
+    let fri_layers_size: usize = (log_last_layer_degree_bound + 1..=log_composition_size)
+        .map(|log_degree| {
+            HASH_SIZE
+                + n_queries * SECURE_FIELD_SIZE
+                + merkle_paths_size(log_degree + log_blowup_factor)
+        })
+        .sum();
+    let last_layer_size = (1 << log_last_layer_degree_bound) * SECURE_FIELD_SIZE;
+
+    // Proof of work nonce, claimed logup sums, and the log size.
+    let other_size = 8 + shape.n_claimed_sums * SECURE_FIELD_SIZE + 4;
+
+    trees_size + sampled_values_size + fri_layers_size + last_layer_size + other_size
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimate_grows_with_queries_and_columns() {
+        let config = PcsConfig::default();
+        let shape = ProofShape {
+            n_columns: [20, 300, 40],
+            n_sampled_values: 400,
+            n_claimed_sums: 4,
+        };
+        let estimate = estimate_proof_size_bytes(16, &config, &shape);
+
+        let mut more_queries = config;
+        more_queries.fri_config.n_queries *= 2;
+        assert!(estimate_proof_size_bytes(16, &more_queries, &shape) > estimate);
+
+        let wider = ProofShape {
+            n_columns: [20, 600, 40],
+            ..shape
+        };
+        assert!(estimate_proof_size_bytes(16, &config, &wider) > estimate);
+        assert!(estimate_proof_size_bytes(20, &config, &shape) > estimate);
+    }
+}
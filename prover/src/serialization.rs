@@ -0,0 +1,86 @@
+//! Stable binary encoding of proofs.
+//!
+//! A serialized proof starts with [`PROOF_MAGIC`] and a [`PROOF_FORMAT_VERSION`] byte, followed by the postcard
+//! encoding of [`Proof`], which holds the commitments, the query responses, and the claimed logup sums.
+
+use std::fmt;
+
+use crate::Proof;
+
+/// Magic number of serialized proofs.
+pub const PROOF_MAGIC: [u8; 4] = *b"NXSP";
+
+/// Version of the proof serialization format, incremented on every change of [`Proof`] encoding.
+pub const PROOF_FORMAT_VERSION: u8 = 1;
+
+const HEADER_LEN: usize = PROOF_MAGIC.len() + 1;
+
+#[derive(Debug)]
+pub enum ProofDeserializeError {
+    /// The bytes don't start with [`PROOF_MAGIC`].
+    InvalidMagic,
+    /// The proof is serialized with a format version other than [`PROOF_FORMAT_VERSION`].
+    UnsupportedVersion(u8),
+    /// The proof body cannot be decoded.
+    Malformed(postcard::Error),
+}
+
+impl fmt::Display for ProofDeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidMagic => write!(f, "not a serialized proof"),
+            Self::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported proof format version {version}, expected {PROOF_FORMAT_VERSION}"
+            ),
+            Self::Malformed(err) => write!(f, "malformed proof: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ProofDeserializeError {}
+
+/// Serializes a proof into the stable binary format.
+pub fn serialize_proof(proof: &Proof) -> Vec<u8> {
+    let mut bytes = Vec::from(PROOF_MAGIC);
+    bytes.push(PROOF_FORMAT_VERSION);
+    bytes.extend(postcard::to_allocvec(proof).expect("proof serialization cannot fail"));
+    bytes
+}
+
+/// Deserializes a proof written by [`serialize_proof`], rejecting any other format version.
+pub fn deserialize_proof(bytes: &[u8]) -> Result<Proof, ProofDeserializeError> {
+    if bytes.len() < HEADER_LEN || bytes[..PROOF_MAGIC.len()] != PROOF_MAGIC {
+        return Err(ProofDeserializeError::InvalidMagic);
+    }
+    let version = bytes[PROOF_MAGIC.len()];
+    if version != PROOF_FORMAT_VERSION {
+        return Err(ProofDeserializeError::UnsupportedVersion(version));
+    }
+    postcard::from_bytes(&bytes[HEADER_LEN..]).map_err(ProofDeserializeError::Malformed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rejects_invalid_headers() {
+        assert!(matches!(
+            deserialize_proof(&[]),
+            Err(ProofDeserializeError::InvalidMagic)
+        ));
+        assert!(matches!(
+            deserialize_proof(b"NXSQ\x01"),
+            Err(ProofDeserializeError::InvalidMagic)
+        ));
+        assert!(matches!(
+            deserialize_proof(&[&PROOF_MAGIC[..], &[PROOF_FORMAT_VERSION + 1, 0, 0]].concat()),
+            Err(ProofDeserializeError::UnsupportedVersion(v)) if v == PROOF_FORMAT_VERSION + 1
+        ));
+        assert!(matches!(
+            deserialize_proof(&[&PROOF_MAGIC[..], &[PROOF_FORMAT_VERSION, 0xFF]].concat()),
+            Err(ProofDeserializeError::Malformed(_))
+        ));
+    }
+}
@@ -1,9 +1,13 @@
+use num_traits::Zero;
 use stwo_prover::{
-    constraint_framework::{assert_constraints, EvalAtRow},
+    constraint_framework::{assert_constraints, EvalAtRow, FrameworkEval, InfoEvaluator},
     core::{
-        backend::simd::SimdBackend,
-        channel::Blake2sChannel,
-        fields::{m31::BaseField, qm31::SecureField},
+        backend::{simd::SimdBackend, BackendForChannel},
+        channel::MerkleChannel,
+        fields::{
+            m31::{self, BaseField},
+            qm31::SecureField,
+        },
         pcs::{CommitmentSchemeProver, PcsConfig, TreeVec},
         poly::{
             circle::{CanonicCoset, CircleEvaluation, PolyOps},
@@ -14,8 +18,12 @@ use stwo_prover::{
 };
 
 use crate::{
-    components::{AllLookupElements, LOG_CONSTRAINT_DEGREE},
-    trace::{program_trace::ProgramTracesBuilder, FinalizedTraces, PreprocessedTraces},
+    column::Column,
+    components::{AllLookupElements, MachineEval, LOG_CONSTRAINT_DEGREE},
+    trace::{
+        eval::INTERACTION_TRACE_IDX, program_trace::ProgramTracesBuilder, FinalizedTraces,
+        PreprocessedTraces,
+    },
     traits::generate_interaction_trace,
 };
 
@@ -40,9 +48,12 @@ pub(crate) fn test_params(
 }
 
 /// Filled out traces, mainly for testing
-pub(crate) struct CommittedTraces<'a> {
-    pub(crate) commitment_scheme: CommitmentSchemeProver<'a, SimdBackend, Blake2sMerkleChannel>,
-    pub(crate) prover_channel: Blake2sChannel,
+pub(crate) struct CommittedTraces<'a, MC: MerkleChannel = Blake2sMerkleChannel>
+where
+    SimdBackend: BackendForChannel<MC>,
+{
+    pub(crate) commitment_scheme: CommitmentSchemeProver<'a, SimdBackend, MC>,
+    pub(crate) prover_channel: MC::C,
     pub(crate) lookup_elements: AllLookupElements,
     pub(crate) preprocessed_trace: PreprocessedTraces,
     pub(crate) interaction_trace: Vec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>>,
@@ -50,16 +61,28 @@ pub(crate) struct CommittedTraces<'a> {
     pub(crate) program_trace: ProgramTraces,
 }
 
-/// Testing utility for filling in traces
+/// Testing utility for filling in traces, commits with [`Blake2sMerkleChannel`].
 pub(crate) fn commit_traces<'a, C: MachineChip>(
     config: PcsConfig,
     twiddles: &'a stwo_prover::core::poly::twiddles::TwiddleTree<SimdBackend>,
     traces: &FinalizedTraces,
     program_traces: Option<ProgramTraces>,
 ) -> CommittedTraces<'a> {
-    let mut commitment_scheme =
-        CommitmentSchemeProver::<_, Blake2sMerkleChannel>::new(config, twiddles);
-    let mut prover_channel = Blake2sChannel::default();
+    commit_traces_with_channel::<C, Blake2sMerkleChannel>(config, twiddles, traces, program_traces)
+}
+
+/// Testing utility for filling in traces, generic over the Merkle channel used for commitments.
+pub(crate) fn commit_traces_with_channel<'a, C: MachineChip, MC: MerkleChannel>(
+    config: PcsConfig,
+    twiddles: &'a stwo_prover::core::poly::twiddles::TwiddleTree<SimdBackend>,
+    traces: &FinalizedTraces,
+    program_traces: Option<ProgramTraces>,
+) -> CommittedTraces<'a, MC>
+where
+    SimdBackend: BackendForChannel<MC>,
+{
+    let mut commitment_scheme = CommitmentSchemeProver::<_, MC>::new(config, twiddles);
+    let mut prover_channel = MC::C::default();
 
     let program_trace =
         program_traces.unwrap_or_else(|| ProgramTracesBuilder::dummy(traces.log_size()).finalize());
@@ -85,6 +108,11 @@ pub(crate) fn commit_traces<'a, C: MachineChip>(
     // Interaction Trace
     let (interaction_trace, claimed_sum) =
         generate_interaction_trace::<C>(traces, &preprocessed_trace, &program_trace, &all_elements);
+    assert_chip_interaction_trace_structure::<C>(
+        &interaction_trace,
+        traces.log_size(),
+        &all_elements,
+    );
     let mut tree_builder = commitment_scheme.tree_builder();
     let _interaction_trace_location = tree_builder.extend_evals(interaction_trace.clone());
     tree_builder.commit(&mut prover_channel);
@@ -100,11 +128,110 @@ pub(crate) fn commit_traces<'a, C: MachineChip>(
     }
 }
 
+/// Asserts that the interaction trace returned by [`MachineChip::fill_interaction_trace`] has as many columns as
+/// the chip's constraints read from [`INTERACTION_TRACE_IDX`], each one `2.pow(log_size)` in length and containing
+/// only reduced field elements.
+pub(crate) fn assert_chip_interaction_trace_structure<C: MachineChip>(
+    interaction_trace: &[CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>],
+    log_size: u32,
+    lookup_elements: &AllLookupElements,
+) {
+    let info =
+        MachineEval::<C>::new(log_size, lookup_elements.clone()).evaluate(InfoEvaluator::empty());
+    let expected_num_cols = info
+        .mask_offsets
+        .get(INTERACTION_TRACE_IDX)
+        .map_or(0, Vec::len);
+    assert_eq!(
+        interaction_trace.len(),
+        expected_num_cols,
+        "interaction trace has {} columns, constraints expect {expected_num_cols}",
+        interaction_trace.len(),
+    );
+    for (col_idx, col) in interaction_trace.iter().enumerate() {
+        assert_eq!(
+            col.domain.log_size(),
+            log_size,
+            "interaction column {col_idx} has invalid length"
+        );
+        for (row, value) in col.values.as_slice().iter().take(1 << log_size).enumerate() {
+            assert!(
+                value.0 < m31::P,
+                "interaction column {col_idx} contains unreduced value {} at row {row}",
+                value.0,
+            );
+        }
+    }
+}
+
+/// Asserts that `traces` match `expected` cell by cell, printing the differing cells otherwise, and then asserts
+/// constraints of the chip, see [`assert_chip`].
+#[cfg(feature = "debug-tools")]
+pub(crate) fn assert_chip_with_expected_trace<C: MachineChip>(
+    traces: TracesBuilder,
+    expected: &TracesBuilder,
+    program_trace: Option<ProgramTraces>,
+) -> (AllLookupElements, SecureField) {
+    let diff = expected.diff(&traces);
+    assert!(diff.is_empty(), "main trace mismatch:\n{diff}");
+    assert_chip::<C>(traces, program_trace)
+}
+
+/// Asserts constraints of the chip, see [`assert_chip`], and then asserts that the logup sum of the chip is balanced
+/// by the components outside of the chip, whose total logup sum is returned by `external_sum`.
+///
+/// This catches chips that emit logup contributions for lookups that are never paid back.
+pub(crate) fn assert_chip_with_logup<C: MachineChip>(
+    traces: TracesBuilder,
+    program_trace: Option<ProgramTraces>,
+    external_sum: impl FnOnce(&AllLookupElements) -> SecureField,
+) -> AllLookupElements {
+    let (lookup_elements, claimed_sum) = assert_chip::<C>(traces, program_trace);
+    let external_sum = external_sum(&lookup_elements);
+    assert!(
+        (claimed_sum + external_sum).is_zero(),
+        "unbalanced logup sum: chip claims {claimed_sum:?}, external components claim {external_sum:?}"
+    );
+    lookup_elements
+}
+
+/// Zeroes out every main trace column outside of `keep`.
+pub(crate) fn mask_columns(traces: &mut TracesBuilder, keep: &[Column]) {
+    let mut kept = vec![false; Column::COLUMNS_NUM];
+    for col in keep {
+        kept[col.offset()..col.offset() + col.size()].fill(true);
+    }
+    for (col, kept) in traces.cols.iter_mut().zip(kept) {
+        if !kept {
+            col.fill(BaseField::zero());
+        }
+    }
+}
+
+/// Asserts constraints of the chip, see [`assert_chip`], on a trace with all columns not owned by the chip zeroed out.
+///
+/// Failures of this assertion are caused by the chip's own columns, rather than by columns shared with other chips.
+pub(crate) fn assert_chip_isolated<C: MachineChip>(
+    mut traces: TracesBuilder,
+    program_trace: Option<ProgramTraces>,
+) -> (AllLookupElements, SecureField) {
+    let owned_columns = C::owned_columns();
+    assert!(
+        !owned_columns.is_empty(),
+        "chip doesn't declare its columns, see MachineChip::owned_columns"
+    );
+    mask_columns(&mut traces, &owned_columns);
+    assert_chip::<C>(traces, program_trace)
+}
+
 /// Assuming traces are filled, assert constraints
 pub(crate) fn assert_chip<C: MachineChip>(
     traces: TracesBuilder,
     program_trace: Option<ProgramTraces>,
 ) -> (AllLookupElements, SecureField) {
+    if let Err(err) = C::validate_witness(&traces) {
+        panic!("invalid witness: {err}");
+    }
     let (config, twiddles) = test_params(traces.log_size());
 
     let finalized_trace = traces.finalize();
@@ -0,0 +1,101 @@
+//! Column statistics of main traces for trace analysis and debugging.
+
+use stwo_prover::core::fields::m31::BaseField;
+
+use super::TracesBuilder;
+use crate::column::Column;
+
+/// Largest value for which [`ColumnStats::value_histogram`] is collected.
+pub const MAX_HISTOGRAM_VALUE: u32 = u8::MAX as u32;
+
+/// Statistics of all raw columns of a single [`Column`], see [`TracesBuilder::column_stats`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnStats {
+    pub min: BaseField,
+    pub max: BaseField,
+    /// Number of non-zero cells.
+    pub nonzero_count: usize,
+    /// Number of cells holding each value, indexed by the value. `None` if the column holds values
+    /// greater than [`MAX_HISTOGRAM_VALUE`].
+    pub value_histogram: Option<Vec<u64>>,
+}
+
+impl TracesBuilder {
+    /// Returns statistics of the cells of `col`, all limbs of a multi-limb column are aggregated.
+    pub fn column_stats(&self, col: Column) -> ColumnStats {
+        let cells = || {
+            self.cols[col.offset()..col.offset() + col.size()]
+                .iter()
+                .flatten()
+        };
+        let min = cells().min_by_key(|value| value.0).copied();
+        let max = cells().max_by_key(|value| value.0).copied();
+        let (min, max) = min.zip(max).expect("columns are never empty");
+        let nonzero_count = cells().filter(|value| value.0 != 0).count();
+
+        let value_histogram = (max.0 <= MAX_HISTOGRAM_VALUE).then(|| {
+            let mut histogram = vec![0u64; max.0 as usize + 1];
+            cells().for_each(|value| histogram[value.0 as usize] += 1);
+            histogram
+        });
+
+        ColumnStats {
+            min,
+            max,
+            nonzero_count,
+            value_histogram,
+        }
+    }
+
+    /// Returns columns without a single non-zero cell.
+    ///
+    /// Dead columns take space in the proof without being used by the traced program.
+    pub fn dead_columns(&self) -> Vec<Column> {
+        Column::ALL_VARIANTS
+            .iter()
+            .copied()
+            .filter(|col| {
+                self.cols[col.offset()..col.offset() + col.size()]
+                    .iter()
+                    .flatten()
+                    .all(|value| value.0 == 0)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::column::Column::{OpA, ValueB};
+
+    const LOG_SIZE: u32 = 5;
+
+    #[test]
+    fn stats_of_filled_columns() {
+        let mut traces = TracesBuilder::new(LOG_SIZE);
+        for row in 0..4 {
+            traces.fill_columns(row, row as u8, OpA);
+        }
+        traces.fill_columns(1, 0x0300u32, ValueB);
+
+        let stats = traces.column_stats(OpA);
+        assert_eq!(stats.min, BaseField::from(0u32));
+        assert_eq!(stats.max, BaseField::from(3u32));
+        assert_eq!(stats.nonzero_count, 3);
+        assert_eq!(stats.value_histogram, Some(vec![29, 1, 1, 1]));
+
+        // four limbs of 32 rows each
+        let stats = traces.column_stats(ValueB);
+        assert_eq!(stats.nonzero_count, 1);
+        assert_eq!(stats.value_histogram, Some(vec![127, 0, 0, 1]));
+
+        *traces.column_mut::<4>(2, ValueB)[0] = BaseField::from(1000u32);
+        assert_eq!(traces.column_stats(ValueB).value_histogram, None);
+
+        let dead_columns = traces.dead_columns();
+        assert!(!dead_columns.contains(&OpA));
+        assert!(!dead_columns.contains(&ValueB));
+        assert_eq!(dead_columns.len(), Column::ALL_VARIANTS.len() - 2);
+    }
+}
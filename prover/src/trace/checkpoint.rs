@@ -0,0 +1,155 @@
+//! Binary serialization of the main trace, allows resuming proving without re-generating the trace.
+//!
+//! The format is a header of little-endian `u32` values `[MAGIC, VERSION, log_size, num_columns]`
+//! followed by every column stored as `2.pow(log_size)` little-endian `u32` values.
+
+use std::io::{self, Read, Write};
+
+use stwo_prover::core::{
+    backend::simd::m31::LOG_N_LANES,
+    fields::m31::{BaseField, P},
+};
+
+use super::TracesBuilder;
+use crate::column::Column;
+
+/// `b"NXTR"` interpreted as a little-endian integer.
+const MAGIC: u32 = u32::from_le_bytes(*b"NXTR");
+/// Format version, must be bumped on any change of the layout.
+const VERSION: u32 = 1;
+/// Upper bound on log_size accepted from the input, prevents huge allocations on corrupted data.
+const MAX_LOG_SIZE: u32 = 28;
+
+fn invalid_data(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+impl TracesBuilder {
+    /// Writes the trace into `writer`.
+    pub fn save(&self, writer: &mut impl Write) -> io::Result<()> {
+        for header in [MAGIC, VERSION, self.log_size, self.cols.len() as u32] {
+            writer.write_all(&header.to_le_bytes())?;
+        }
+        let mut buf = Vec::with_capacity(self.num_rows() * 4);
+        for col in &self.cols {
+            buf.clear();
+            buf.extend(col.iter().flat_map(|value| value.0.to_le_bytes()));
+            writer.write_all(&buf)?;
+        }
+        Ok(())
+    }
+
+    /// Reads the trace previously written with [`TracesBuilder::save`].
+    ///
+    /// Returns [`io::ErrorKind::InvalidData`] error if the header doesn't match the current format,
+    /// the number of columns differs from [`Column::COLUMNS_NUM`], or any value isn't a reduced field element.
+    pub fn load(reader: &mut impl Read) -> io::Result<Self> {
+        let magic = read_u32(reader)?;
+        if magic != MAGIC {
+            return Err(invalid_data(format!("invalid magic number {magic:#010x}")));
+        }
+        let version = read_u32(reader)?;
+        if version != VERSION {
+            return Err(invalid_data(format!(
+                "unsupported format version {version}, expected {VERSION}"
+            )));
+        }
+        let log_size = read_u32(reader)?;
+        if !(LOG_N_LANES..=MAX_LOG_SIZE).contains(&log_size) {
+            return Err(invalid_data(format!("invalid log_size {log_size}")));
+        }
+        let num_cols = read_u32(reader)? as usize;
+        if num_cols != Column::COLUMNS_NUM {
+            return Err(invalid_data(format!(
+                "trace has {num_cols} columns, expected {}",
+                Column::COLUMNS_NUM
+            )));
+        }
+
+        let mut buf = vec![0u8; 4 << log_size];
+        let cols: Vec<Vec<BaseField>> = (0..num_cols)
+            .map(|_| {
+                reader.read_exact(&mut buf)?;
+                buf.chunks_exact(4)
+                    .map(|bytes| {
+                        let value = u32::from_le_bytes(bytes.try_into().expect("chunk size is 4"));
+                        if value >= P {
+                            return Err(invalid_data(format!("unreduced field element {value}")));
+                        }
+                        Ok(BaseField::from(value))
+                    })
+                    .collect::<io::Result<Vec<_>>>()
+            })
+            .collect::<io::Result<_>>()?;
+        Ok(Self { cols, log_size })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::column::Column::{OpA, ValueB};
+
+    const LOG_SIZE: u32 = 6;
+
+    fn filled_traces() -> TracesBuilder {
+        let mut traces = TracesBuilder::new(LOG_SIZE);
+        for row in 0..traces.num_rows() {
+            traces.fill_columns(row, BaseField::from(P - 1 - row as u32), OpA);
+            traces.fill_columns(row, (row as u32).wrapping_mul(0x9e3779b9), ValueB);
+        }
+        traces
+    }
+
+    #[test]
+    fn round_trip() {
+        let traces = filled_traces();
+        let mut bytes = Vec::new();
+        traces.save(&mut bytes).unwrap();
+        assert_eq!(
+            bytes.len(),
+            16 + Column::COLUMNS_NUM * 4 * traces.num_rows()
+        );
+
+        let loaded = TracesBuilder::load(&mut bytes.as_slice()).unwrap();
+        assert_eq!(loaded.log_size(), traces.log_size());
+        assert_eq!(loaded.cols, traces.cols);
+
+        let loaded = loaded.finalize().into_circle_evaluation();
+        let original = traces.finalize().into_circle_evaluation();
+        for (loaded, original) in loaded.iter().zip(&original) {
+            assert_eq!(loaded.values.as_slice(), original.values.as_slice());
+        }
+    }
+
+    #[test]
+    fn load_rejects_invalid_input() {
+        let mut bytes = Vec::new();
+        filled_traces().save(&mut bytes).unwrap();
+
+        let load = |bytes: &[u8]| TracesBuilder::load(&mut &bytes[..]).unwrap_err().kind();
+
+        let mut invalid_magic = bytes.clone();
+        invalid_magic[0] ^= 1;
+        assert_eq!(load(&invalid_magic), io::ErrorKind::InvalidData);
+
+        let mut invalid_version = bytes.clone();
+        invalid_version[4..8].copy_from_slice(&(VERSION + 1).to_le_bytes());
+        assert_eq!(load(&invalid_version), io::ErrorKind::InvalidData);
+
+        let mut unreduced = bytes.clone();
+        unreduced[16..20].copy_from_slice(&P.to_le_bytes());
+        assert_eq!(load(&unreduced), io::ErrorKind::InvalidData);
+
+        assert_eq!(
+            load(&bytes[..bytes.len() - 1]),
+            io::ErrorKind::UnexpectedEof
+        );
+    }
+}
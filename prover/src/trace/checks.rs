@@ -0,0 +1,672 @@
+//! Row-wise checks over the main trace.
+//!
+//! These helpers operate directly on raw columns of [`TracesBuilder`] and are meant for validating
+//! witness generation in tests, before the trace is committed and constraints are evaluated.
+
+use std::collections::{HashMap, HashSet};
+
+use nexus_common::constants::NUM_REGISTERS;
+use num_traits::{One, Zero};
+use stwo_prover::core::fields::{m31::BaseField, FieldExpOps};
+
+use super::TracesBuilder;
+use crate::column::Column;
+
+impl TracesBuilder {
+    /// Returns the raw column of `col`.
+    ///
+    /// Panics if `col` doesn't consist of exactly one raw column.
+    fn single_column(&self, col: Column) -> &[BaseField] {
+        assert_eq!(col.size(), 1, "{col:?} must be a single column");
+        &self.cols[col.offset()]
+    }
+
+    /// Returns `true` if `a_1 * c_1 + a_2 * c_2 + ... + a_k * c_k` is zero on every row, where `cols`
+    /// contains `(c_i, a_i)` pairs of columns and their coefficients.
+    ///
+    /// All referenced columns must have size 1.
+    pub fn assert_column_linear_combination_is_zero(&self, cols: &[(Column, BaseField)]) -> bool {
+        let terms: Vec<(&[BaseField], BaseField)> = cols
+            .iter()
+            .map(|&(col, coeff)| (self.single_column(col), coeff))
+            .collect();
+        (0..self.num_rows()).all(|row| {
+            terms
+                .iter()
+                .fold(BaseField::zero(), |acc, (col, coeff)| {
+                    acc + col[row] * *coeff
+                })
+                .is_zero()
+        })
+    }
+
+    /// Returns the number of distinct values stored in `col`.
+    ///
+    /// `col` must have size 1.
+    pub fn column_distinct_count(&self, col: Column) -> usize {
+        self.single_column(col)
+            .iter()
+            .map(|value| value.0)
+            .collect::<HashSet<u32>>()
+            .len()
+    }
+
+    /// Returns the number of occurrences of every value stored in `col`.
+    ///
+    /// `col` must have size 1.
+    pub fn column_frequency_table(&self, col: Column) -> HashMap<u32, usize> {
+        let mut table = HashMap::new();
+        for value in self.single_column(col) {
+            *table.entry(value.0).or_insert(0) += 1;
+        }
+        table
+    }
+
+    /// Returns up to `k` most frequent values of `col` along with their number of occurrences, sorted by
+    /// decreasing count. Values with equal counts are ordered by increasing value.
+    ///
+    /// `col` must have size 1.
+    pub fn column_top_k(&self, col: Column, k: usize) -> Vec<(u32, usize)> {
+        if k == 0 {
+            return Vec::new();
+        }
+        let mut frequencies: Vec<(u32, usize)> =
+            self.column_frequency_table(col).into_iter().collect();
+        let by_count = |a: &(u32, usize), b: &(u32, usize)| b.1.cmp(&a.1).then(a.0.cmp(&b.0));
+
+        if k < frequencies.len() {
+            frequencies.select_nth_unstable_by(k - 1, by_count);
+            frequencies.truncate(k);
+        }
+        frequencies.sort_unstable_by(by_count);
+        frequencies
+    }
+
+    /// Applies `f` to corresponding values of `col_a` and `col_b` and returns the results.
+    ///
+    /// Both columns must have size 1.
+    pub fn column_zip<F>(&self, col_a: Column, col_b: Column, f: F) -> Vec<BaseField>
+    where
+        F: Fn(BaseField, BaseField) -> BaseField,
+    {
+        let a = self.single_column(col_a);
+        let b = self.single_column(col_b);
+        a.iter().zip(b).map(|(&a, &b)| f(a, b)).collect()
+    }
+
+    /// Returns `col_a[i] + col_b[i]` for every row.
+    pub fn column_add(&self, col_a: Column, col_b: Column) -> Vec<BaseField> {
+        self.column_zip(col_a, col_b, |a, b| a + b)
+    }
+
+    /// Returns `col_a[i] - col_b[i]` for every row.
+    pub fn column_sub(&self, col_a: Column, col_b: Column) -> Vec<BaseField> {
+        self.column_zip(col_a, col_b, |a, b| a - b)
+    }
+
+    /// Returns `col_a[i] * col_b[i]` for every row.
+    pub fn column_mul(&self, col_a: Column, col_b: Column) -> Vec<BaseField> {
+        self.column_zip(col_a, col_b, |a, b| a * b)
+    }
+
+    /// Returns `true` if every value of `query_col` occurs somewhere in `table_col`.
+    ///
+    /// The first missing value and its row are printed to stderr. Both columns must have size 1.
+    pub fn column_verify_lookup_argument(&self, query_col: Column, table_col: Column) -> bool {
+        let table: HashSet<u32> = self
+            .single_column(table_col)
+            .iter()
+            .map(|value| value.0)
+            .collect();
+        let missing = self
+            .single_column(query_col)
+            .iter()
+            .enumerate()
+            .find(|(_, value)| !table.contains(&value.0));
+        if let Some((row, value)) = missing {
+            eprintln!("row {row}: {query_col:?} = {value} is missing in {table_col:?}");
+            return false;
+        }
+        true
+    }
+
+    /// Asserts that every value of `query_col` occurs somewhere in `table_col`.
+    pub fn assert_lookup_argument(&self, query_col: Column, table_col: Column) {
+        assert!(
+            self.column_verify_lookup_argument(query_col, table_col),
+            "lookup of {query_col:?} into {table_col:?} failed"
+        );
+    }
+
+    /// Calls `f(col[i].0, col[i + 1].0)` for every pair of consecutive rows and returns `(i, col[i].0, col[i + 1].0)`
+    /// for each pair that doesn't satisfy it.
+    ///
+    /// If `wrap` is `true`, the pair of the last and the first rows is checked as well. `col` must have size 1.
+    pub fn column_check_transition_function<F>(
+        &self,
+        col: Column,
+        wrap: bool,
+        f: F,
+    ) -> Vec<(usize, u32, u32)>
+    where
+        F: Fn(u32, u32) -> bool,
+    {
+        let values = self.single_column(col);
+        let num_rows = values.len();
+        let num_pairs = if wrap { num_rows } else { num_rows - 1 };
+        (0..num_pairs)
+            .map(|row| (row, values[row].0, values[(row + 1) % num_rows].0))
+            .filter(|&(_, curr, next)| !f(curr, next))
+            .collect()
+    }
+
+    /// Returns multiplicative inverses of values stored in `col`, zero values are mapped to zero.
+    ///
+    /// Uses Montgomery's trick, which requires a single field inversion for the whole column.
+    /// `col` must have size 1.
+    pub fn column_inverse(&self, col: Column) -> Vec<BaseField> {
+        let values = self.single_column(col);
+
+        // prefix_products[i] is the product of all non-zero values in rows 0..i
+        let mut prefix_products = Vec::with_capacity(values.len());
+        let mut acc = BaseField::one();
+        for value in values {
+            prefix_products.push(acc);
+            if !value.is_zero() {
+                acc *= *value;
+            }
+        }
+
+        let mut inv_acc = acc.inverse();
+        let mut inverses = vec![BaseField::zero(); values.len()];
+        for row in (0..values.len()).rev() {
+            if values[row].is_zero() {
+                continue;
+            }
+            inverses[row] = inv_acc * prefix_products[row];
+            inv_acc *= values[row];
+        }
+        inverses
+    }
+
+    /// Applies `predicate` to every window of `window_size` consecutive rows of `col` and returns starting rows
+    /// of windows that don't satisfy it.
+    ///
+    /// If `wrap` is `true`, windows starting near the last row wrap around to the first row, otherwise only windows
+    /// that fit into the trace are checked. `col` must have size 1.
+    pub fn column_windowed_check<F>(
+        &self,
+        col: Column,
+        window_size: usize,
+        wrap: bool,
+        predicate: F,
+    ) -> Vec<usize>
+    where
+        F: Fn(&[BaseField]) -> bool,
+    {
+        let values = self.single_column(col);
+        let num_rows = values.len();
+        assert!(
+            window_size > 0 && window_size <= num_rows,
+            "window size must be in range 1..={num_rows}"
+        );
+        let num_windows = if wrap {
+            num_rows
+        } else {
+            num_rows - window_size + 1
+        };
+
+        let mut window = Vec::with_capacity(window_size);
+        (0..num_windows)
+            .filter(|&start| {
+                window.clear();
+                window.extend((start..start + window_size).map(|row| values[row % num_rows]));
+                !predicate(&window)
+            })
+            .collect()
+    }
+
+    /// Asserts that `product_col[i] == col_a[i] * col_b[i]` on every row.
+    ///
+    /// All three columns must have size 1.
+    pub fn assert_column_product_equals_column(
+        &self,
+        col_a: Column,
+        col_b: Column,
+        product_col: Column,
+    ) {
+        let products = self.single_column(product_col);
+        for (row, (expected, actual)) in self
+            .column_mul(col_a, col_b)
+            .iter()
+            .zip(products)
+            .enumerate()
+        {
+            let [a] = self.column(row, col_a);
+            let [b] = self.column(row, col_b);
+            assert_eq!(
+                actual, expected,
+                "row {row}: {col_a:?} = {a}, {col_b:?} = {b}, expected {product_col:?} = {expected}, got {actual}"
+            );
+        }
+    }
+
+    /// Asserts that `col` stores the geometric series `start, start * ratio, start * ratio^2, ...`.
+    ///
+    /// `col` must have size 1.
+    pub fn assert_column_geometric_series(&self, col: Column, ratio: BaseField, start: BaseField) {
+        let mut expected = start;
+        for (row, actual) in self.single_column(col).iter().enumerate() {
+            assert_eq!(
+                *actual, expected,
+                "row {row}: {col:?} = {actual} doesn't match expected {expected}"
+            );
+            expected *= ratio;
+        }
+    }
+
+    /// Asserts that every row of `col` starting from `num_active` is zero.
+    ///
+    /// Intended to be called after witness generation for chips that leave padding rows unfilled.
+    pub fn assert_column_is_zero_for_padded_rows(&self, col: Column, num_active: usize) {
+        let num_rows = self.num_rows();
+        assert!(
+            num_active <= num_rows,
+            "number of active rows {num_active} exceeds trace size {num_rows}"
+        );
+        let cols = &self.cols[col.offset()..col.offset() + col.size()];
+        for row in num_active..num_rows {
+            assert!(
+                cols.iter().all(|col| col[row].is_zero()),
+                "padding row {row}: {col:?} is non-zero"
+            );
+        }
+    }
+
+    /// Returns `(row, value)` pairs of `col` whose values aren't valid register indices.
+    fn invalid_register_indices(&self, col: Column) -> Vec<(usize, u32)> {
+        self.single_column(col)
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| value.0 >= NUM_REGISTERS)
+            .map(|(row, value)| (row, value.0))
+            .collect()
+    }
+
+    /// Asserts that every value of `col` is a register index in range `0..NUM_REGISTERS`.
+    ///
+    /// `col` must have size 1.
+    pub fn assert_register_index_column(&self, col: Column) {
+        if let Some(&(row, value)) = self.invalid_register_indices(col).first() {
+            panic!("row {row}: {col:?} = {value} is not a valid register index");
+        }
+    }
+
+    /// Asserts that `rs1_col`, `rs2_col` and `rd_col` only contain register indices in range `0..NUM_REGISTERS`.
+    ///
+    /// On failure, reports the total number of violations across all three columns and the first one found.
+    pub fn assert_columns_form_valid_register_values(
+        &self,
+        rs1_col: Column,
+        rs2_col: Column,
+        rd_col: Column,
+    ) {
+        let violations: Vec<(Column, usize, u32)> = [rs1_col, rs2_col, rd_col]
+            .into_iter()
+            .flat_map(|col| {
+                self.invalid_register_indices(col)
+                    .into_iter()
+                    .map(move |(row, value)| (col, row, value))
+            })
+            .collect();
+        if let Some(&(col, row, value)) = violations.first() {
+            panic!(
+                "found {} invalid register indices, first at row {row}: {col:?} = {value}",
+                violations.len()
+            );
+        }
+    }
+
+    /// Asserts that `col_a[i] % modulus == col_b[i]` on every row, treating both columns as `u32` values.
+    ///
+    /// Both columns must have size 1.
+    pub fn assert_column_mod_constraint(&self, col_a: Column, col_b: Column, modulus: u32) {
+        assert_ne!(modulus, 0, "modulus must be non-zero");
+        let a = self.single_column(col_a);
+        let b = self.single_column(col_b);
+        for (row, (a, b)) in a.iter().zip(b).enumerate() {
+            assert_eq!(
+                a.0 % modulus,
+                b.0,
+                "row {row}: {col_a:?} = {} mod {modulus} doesn't match {col_b:?} = {}",
+                a.0,
+                b.0,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use stwo_prover::core::fields::m31::P;
+
+    use super::*;
+    use crate::column::Column::{OpA, OpB, OpC, ValueA};
+
+    const LOG_SIZE: u32 = 6;
+
+    #[test]
+    fn linear_combination_is_zero() {
+        let mut traces = TracesBuilder::new(LOG_SIZE);
+        for row in 0..traces.num_rows() {
+            let a = BaseField::from(row as u32);
+            traces.fill_columns(row, a, OpA);
+            traces.fill_columns(row, a + a, OpB);
+            traces.fill_columns(row, a + a + a, OpC);
+        }
+        let one = BaseField::from(1u32);
+        let two = BaseField::from(2u32);
+
+        // 2 * op_a - op_b = 0
+        assert!(traces.assert_column_linear_combination_is_zero(&[(OpA, two), (OpB, -one)]));
+        // op_a + op_b - op_c = 0
+        assert!(traces.assert_column_linear_combination_is_zero(&[
+            (OpA, one),
+            (OpB, one),
+            (OpC, -one)
+        ]));
+        // op_a - op_b != 0 on non-zero rows
+        assert!(!traces.assert_column_linear_combination_is_zero(&[(OpA, one), (OpB, -one)]));
+        // empty combination is trivially zero
+        assert!(traces.assert_column_linear_combination_is_zero(&[]));
+    }
+
+    #[test]
+    fn distinct_count() {
+        let mut traces = TracesBuilder::new(LOG_SIZE);
+        assert_eq!(traces.column_distinct_count(OpA), 1);
+
+        for row in 0..traces.num_rows() {
+            traces.fill_columns(row, (row % 10) as u8, OpA);
+            traces.fill_columns(row, row as u8, OpB);
+        }
+        assert_eq!(traces.column_distinct_count(OpA), 10);
+        assert_eq!(traces.column_distinct_count(OpB), traces.num_rows());
+    }
+
+    #[test]
+    fn top_k() {
+        let mut traces = TracesBuilder::new(LOG_SIZE);
+        for row in 0..traces.num_rows() {
+            // value v occurs v + 1 times for v < 10, the rest of the column is zero
+            let value = (0..10u8).find(|&v| row < (v as usize + 1) * (v as usize + 2) / 2);
+            traces.fill_columns(row, value.unwrap_or(0), OpA);
+        }
+        let table = traces.column_frequency_table(OpA);
+        assert_eq!(table.values().sum::<usize>(), traces.num_rows());
+        assert_eq!(table[&9], 10);
+
+        assert_eq!(traces.column_top_k(OpA, 0), vec![]);
+        // 64 rows: zero fills rows 0 and 55..64
+        assert_eq!(traces.column_top_k(OpA, 3), vec![(0, 10), (9, 10), (8, 9)]);
+        assert_eq!(traces.column_top_k(OpA, 100).len(), 10);
+    }
+
+    #[test]
+    fn mod_constraint() {
+        let mut traces = TracesBuilder::new(LOG_SIZE);
+        for row in 0..traces.num_rows() {
+            let a = BaseField::from(P - 1 - row as u32);
+            traces.fill_columns(row, a, OpA);
+        }
+        // every value is congruent to zero modulo 1
+        traces.assert_column_mod_constraint(OpA, OpB, 1);
+
+        // all values are smaller than the modulus
+        traces.assert_column_mod_constraint(OpA, OpA, P);
+        traces.assert_column_mod_constraint(OpA, OpA, u32::MAX);
+
+        for row in 0..traces.num_rows() {
+            let [a] = traces.column(row, OpA);
+            traces.fill_columns(row, BaseField::from(a.0 % 256), OpB);
+        }
+        traces.assert_column_mod_constraint(OpA, OpB, 256);
+    }
+
+    #[test]
+    #[should_panic(expected = "row 1")]
+    fn mod_constraint_violated() {
+        let mut traces = TracesBuilder::new(LOG_SIZE);
+        traces.fill_columns(1, 7u8, OpA);
+        traces.fill_columns(1, 3u8, OpB);
+        traces.assert_column_mod_constraint(OpA, OpB, 5);
+    }
+
+    #[test]
+    fn product_equals_column() {
+        let mut traces = TracesBuilder::new(LOG_SIZE);
+        for row in 0..traces.num_rows() {
+            let a = BaseField::from(row as u32 + (1 << 30));
+            let b = BaseField::from(row as u32 + 5);
+            traces.fill_columns(row, a, OpA);
+            traces.fill_columns(row, b, OpB);
+            traces.fill_columns(row, a * b, OpC);
+        }
+        traces.assert_column_product_equals_column(OpA, OpB, OpC);
+        traces.assert_column_product_equals_column(OpB, OpA, OpC);
+    }
+
+    #[test]
+    #[should_panic(expected = "row 2: OpA = 2, OpB = 2, expected OpC = 4, got 5")]
+    fn product_equals_column_violated() {
+        let mut traces = TracesBuilder::new(LOG_SIZE);
+        for row in 0..traces.num_rows() {
+            traces.fill_columns(row, row as u8, OpA);
+            traces.fill_columns(row, row as u8, OpB);
+            traces.fill_columns(row, BaseField::from((row * row) as u32), OpC);
+        }
+        traces.fill_columns(2, 5u8, OpC);
+        traces.assert_column_product_equals_column(OpA, OpB, OpC);
+    }
+
+    #[test]
+    fn geometric_series() {
+        let mut traces = TracesBuilder::new(LOG_SIZE);
+        let two = BaseField::from(2u32);
+        let mut value = BaseField::from(3u32);
+        for row in 0..traces.num_rows() {
+            traces.fill_columns(row, value, OpA);
+            value *= two;
+        }
+        // powers of two wrap around the modulus after 31 rows
+        traces.assert_column_geometric_series(OpA, two, BaseField::from(3u32));
+        traces.assert_column_geometric_series(OpB, two, BaseField::zero());
+    }
+
+    #[test]
+    #[should_panic(expected = "row 3")]
+    fn geometric_series_violated() {
+        let mut traces = TracesBuilder::new(LOG_SIZE);
+        for row in 0..traces.num_rows() {
+            traces.fill_columns(row, BaseField::from(1u32), OpA);
+        }
+        traces.fill_columns(3, 2u8, OpA);
+        traces.assert_column_geometric_series(OpA, BaseField::from(1u32), BaseField::from(1u32));
+    }
+
+    #[test]
+    fn zip_columns() {
+        let mut traces = TracesBuilder::new(LOG_SIZE);
+        for row in 0..traces.num_rows() {
+            traces.fill_columns(row, row as u8, OpA);
+            traces.fill_columns(row, 3u8, OpB);
+        }
+        let expected = |f: fn(u32) -> u32| {
+            (0..traces.num_rows() as u32)
+                .map(|row| BaseField::from(f(row)))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(traces.column_add(OpA, OpB), expected(|a| a + 3));
+        assert_eq!(traces.column_mul(OpA, OpB), expected(|a| a * 3));
+        assert_eq!(
+            traces.column_sub(OpB, OpA),
+            expected(|a| (BaseField::from(3u32) - BaseField::from(a)).0)
+        );
+        assert_eq!(
+            traces.column_zip(OpA, OpB, |a, b| BaseField::from(a.0 ^ b.0)),
+            expected(|a| a ^ 3)
+        );
+    }
+
+    #[test]
+    fn inverse() {
+        let mut traces = TracesBuilder::new(LOG_SIZE);
+        assert!(traces.column_inverse(OpA).iter().all(Zero::is_zero));
+
+        for row in 0..traces.num_rows() {
+            // every fourth row is left zero
+            if row % 4 != 0 {
+                traces.fill_columns(row, BaseField::from(P - row as u32), OpA);
+            }
+        }
+        let inverses = traces.column_inverse(OpA);
+        for (row, inv) in inverses.iter().enumerate() {
+            let [value] = traces.column(row, OpA);
+            if row % 4 == 0 {
+                assert!(inv.is_zero());
+            } else {
+                assert_eq!(*inv, value.inverse());
+            }
+        }
+    }
+
+    #[test]
+    fn zero_for_padded_rows() {
+        let mut traces = TracesBuilder::new(LOG_SIZE);
+        let num_rows = traces.num_rows();
+        traces.assert_column_is_zero_for_padded_rows(ValueA, 0);
+
+        for row in 0..10 {
+            traces.fill_columns(row, row as u32 + 1, ValueA);
+        }
+        traces.assert_column_is_zero_for_padded_rows(ValueA, 10);
+        traces.assert_column_is_zero_for_padded_rows(ValueA, num_rows);
+    }
+
+    #[test]
+    #[should_panic(expected = "padding row 9")]
+    fn non_zero_padded_row() {
+        let mut traces = TracesBuilder::new(LOG_SIZE);
+        traces.fill_columns(9, 1u32 << 24, ValueA);
+        traces.assert_column_is_zero_for_padded_rows(ValueA, 5);
+    }
+
+    #[test]
+    fn transition_function() {
+        let mut traces = TracesBuilder::new(LOG_SIZE);
+        let num_rows = traces.num_rows();
+        for row in 0..num_rows {
+            traces.fill_columns(row, BaseField::from(row as u32 * 4), OpA);
+        }
+        let pc_step = |curr: u32, next: u32| next == curr + 4;
+        assert!(traces
+            .column_check_transition_function(OpA, false, pc_step)
+            .is_empty());
+        assert_eq!(
+            traces.column_check_transition_function(OpA, true, pc_step),
+            vec![(num_rows - 1, (num_rows as u32 - 1) * 4, 0)]
+        );
+
+        traces.fill_columns(20, 0u8, OpA);
+        assert_eq!(
+            traces.column_check_transition_function(OpA, false, pc_step),
+            vec![(19, 76, 0), (20, 0, 84)]
+        );
+    }
+
+    #[test]
+    fn register_values() {
+        let mut traces = TracesBuilder::new(LOG_SIZE);
+        for row in 0..traces.num_rows() {
+            traces.fill_columns(row, (row % 32) as u8, OpA);
+            traces.fill_columns(row, (31 - row % 32) as u8, OpB);
+        }
+        traces.assert_register_index_column(OpA);
+        traces.assert_columns_form_valid_register_values(OpB, OpC, OpA);
+    }
+
+    #[test]
+    #[should_panic(expected = "found 3 invalid register indices, first at row 2: OpB = 32")]
+    fn invalid_register_values() {
+        let mut traces = TracesBuilder::new(LOG_SIZE);
+        traces.fill_columns(5, 40u8, OpA);
+        traces.fill_columns(2, 32u8, OpB);
+        traces.fill_columns(7, 255u8, OpB);
+        traces.assert_columns_form_valid_register_values(OpB, OpC, OpA);
+    }
+
+    #[test]
+    fn lookup_argument() {
+        let mut traces = TracesBuilder::new(LOG_SIZE);
+        for row in 0..traces.num_rows() {
+            traces.fill_columns(row, row as u8, OpA);
+            traces.fill_columns(row, (row * 7 % 64) as u8, OpB);
+        }
+        traces.assert_lookup_argument(OpB, OpA);
+        // zeroed column only queries zero
+        traces.assert_lookup_argument(OpC, OpA);
+        assert!(!traces.column_verify_lookup_argument(OpA, OpC));
+
+        traces.fill_columns(40, 64u8, OpB);
+        assert!(!traces.column_verify_lookup_argument(OpB, OpA));
+    }
+
+    #[test]
+    #[should_panic(expected = "lookup of OpA into OpB failed")]
+    fn lookup_argument_violated() {
+        let mut traces = TracesBuilder::new(LOG_SIZE);
+        traces.fill_columns(3, 1u8, OpA);
+        traces.assert_lookup_argument(OpA, OpB);
+    }
+
+    #[test]
+    fn windowed_check() {
+        let mut traces = TracesBuilder::new(LOG_SIZE);
+        let num_rows = traces.num_rows();
+        for row in 0..num_rows {
+            traces.fill_columns(row, row as u8, OpA);
+        }
+        let one = BaseField::from(1u32);
+        let increments = |w: &[BaseField]| w.windows(2).all(|pair| pair[1] == pair[0] + one);
+
+        assert!(traces
+            .column_windowed_check(OpA, 2, false, increments)
+            .is_empty());
+        assert_eq!(
+            traces.column_windowed_check(OpA, 2, true, increments),
+            vec![num_rows - 1]
+        );
+        assert_eq!(
+            traces.column_windowed_check(OpA, 3, true, increments),
+            vec![num_rows - 2, num_rows - 1]
+        );
+
+        // break the chain in the middle
+        traces.fill_columns(10, 0u8, OpA);
+        assert_eq!(
+            traces.column_windowed_check(OpA, 3, false, increments),
+            vec![8, 9, 10]
+        );
+        assert_eq!(
+            traces.column_windowed_check(OpA, num_rows, false, |_| true),
+            Vec::<usize>::new()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "must be a single column")]
+    fn linear_combination_rejects_word_columns() {
+        let traces = TracesBuilder::new(LOG_SIZE);
+        traces.assert_column_linear_combination_is_zero(&[(ValueA, BaseField::from(1u32))]);
+    }
+}
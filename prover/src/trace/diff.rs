@@ -0,0 +1,137 @@
+//! Cell-by-cell comparison of main traces for debugging constraint failures.
+
+use std::fmt;
+
+use stwo_prover::core::fields::m31::BaseField;
+
+use super::TracesBuilder;
+use crate::column::Column;
+
+/// Maximum number of differing cells recorded by [`TracesBuilder::diff`].
+pub const MAX_DIFF_CELLS: usize = 16;
+
+/// A single trace cell that differs between two traces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellDiff {
+    pub row: usize,
+    pub col: Column,
+    /// Index of the raw column within `col`.
+    pub limb: usize,
+    pub expected: BaseField,
+    pub actual: BaseField,
+}
+
+/// Result of [`TracesBuilder::diff`]: the first [`MAX_DIFF_CELLS`] differing cells in row-major order,
+/// and the total number of differences.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TraceDiff {
+    pub cells: Vec<CellDiff>,
+    pub num_diffs: usize,
+}
+
+impl TraceDiff {
+    /// Returns `true` if the traces are identical.
+    pub fn is_empty(&self) -> bool {
+        self.num_diffs == 0
+    }
+}
+
+impl fmt::Display for CellDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "row {}, column {:?}[{}]: expected {:#010x}, got {:#010x}",
+            self.row, self.col, self.limb, self.expected.0, self.actual.0
+        )
+    }
+}
+
+impl fmt::Display for TraceDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "traces are identical");
+        }
+        for cell in &self.cells {
+            writeln!(f, "{cell}")?;
+        }
+        let omitted = self.num_diffs - self.cells.len();
+        if omitted > 0 {
+            writeln!(f, "... and {omitted} more differing cells")?;
+        }
+        Ok(())
+    }
+}
+
+impl TracesBuilder {
+    /// Compares `self` (expected) with `other` (actual) cell by cell.
+    ///
+    /// Panics if traces have different log sizes.
+    pub fn diff(&self, other: &TracesBuilder) -> TraceDiff {
+        assert_eq!(self.log_size, other.log_size, "log_size mismatch");
+
+        let mut diff = TraceDiff::default();
+        for row in 0..self.num_rows() {
+            for &col in Column::ALL_VARIANTS {
+                for limb in 0..col.size() {
+                    let expected = self.cols[col.offset() + limb][row];
+                    let actual = other.cols[col.offset() + limb][row];
+                    if expected == actual {
+                        continue;
+                    }
+                    diff.num_diffs += 1;
+                    if diff.cells.len() < MAX_DIFF_CELLS {
+                        diff.cells.push(CellDiff {
+                            row,
+                            col,
+                            limb,
+                            expected,
+                            actual,
+                        });
+                    }
+                }
+            }
+        }
+        diff
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::column::Column::{OpA, ValueB};
+
+    const LOG_SIZE: u32 = 5;
+
+    #[test]
+    fn diff_traces() {
+        let expected = TracesBuilder::new(LOG_SIZE);
+        let mut actual = expected.clone();
+        assert!(expected.diff(&actual).is_empty());
+
+        actual.fill_columns(3, 0x0100u32, ValueB);
+        for row in 10..30 {
+            actual.fill_columns(row, 1u8, OpA);
+        }
+        let diff = expected.diff(&actual);
+        assert_eq!(diff.num_diffs, 21);
+        assert_eq!(diff.cells.len(), MAX_DIFF_CELLS);
+
+        let output = diff.to_string();
+        let mut lines = output.lines();
+        assert_eq!(
+            lines.next(),
+            Some("row 3, column ValueB[1]: expected 0x00000000, got 0x00000001")
+        );
+        assert_eq!(
+            lines.next(),
+            Some("row 10, column OpA[0]: expected 0x00000000, got 0x00000001")
+        );
+        assert_eq!(lines.last(), Some("... and 5 more differing cells"));
+    }
+
+    #[test]
+    #[should_panic(expected = "log_size mismatch")]
+    fn diff_different_sizes() {
+        TracesBuilder::new(LOG_SIZE).diff(&TracesBuilder::new(LOG_SIZE + 1));
+    }
+}
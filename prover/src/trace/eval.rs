@@ -1,6 +1,6 @@
 use std::array;
 
-use num_traits::Zero;
+use num_traits::{One, Zero};
 use stwo_prover::constraint_framework::{preprocessed_columns::PreProcessedColumnId, EvalAtRow};
 
 use crate::column::{
@@ -76,6 +76,37 @@ impl<E: EvalAtRow> TraceEval<E> {
         array::from_fn(|i| self.preprocessed_evals[offset + i].clone())
     }
 
+    /// Returns the evaluation of [`PreprocessedColumn::IsFirst`], which is one on the first row and zero elsewhere.
+    ///
+    /// ```ignore
+    /// let trace_eval = TraceEval::new(&mut eval);
+    /// let pc = trace_eval!(trace_eval, Column::Pc);
+    /// let is_first = trace_eval.is_first_row();
+    /// eval.add_constraint(is_first * pc[0].clone());
+    /// ```
+    pub fn is_first_row(&self) -> E::F {
+        let [is_first] = self.preprocessed_column_eval(PreprocessedColumn::IsFirst);
+        is_first
+    }
+
+    /// Adds the constraint `x * (x - 1) = 0` for every limb `x` of `col` at the current row.
+    pub fn assert_boolean(&self, eval: &mut E, col: Column) {
+        let offset = col.offset();
+        for [limb, _] in &self.evals[offset..offset + col.size()] {
+            eval.add_constraint(limb.clone() * (limb.clone() - E::F::one()));
+        }
+    }
+
+    /// Adds the constraint `selector * x * (x - 1) = 0` for every limb `x` of `col` at the current row.
+    ///
+    /// On rows where `selector` is zero `col` is unconstrained, otherwise `selector` is expected to be one.
+    pub fn assert_boolean_or_zero(&self, eval: &mut E, col: Column, selector: E::F) {
+        let offset = col.offset();
+        for [limb, _] in &self.evals[offset..offset + col.size()] {
+            eval.add_constraint(selector.clone() * limb.clone() * (limb.clone() - E::F::one()));
+        }
+    }
+
     // #[doc(hidden)]
     // pub fn preprocessed_column_eval_next_row<const N: usize>(
     //     &self,
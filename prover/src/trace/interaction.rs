@@ -0,0 +1,129 @@
+//! Row-oriented construction of logup interaction traces.
+//!
+//! `InteractionTraceBuilder` accumulates fractions `multiplicity / combine(values)` for every vectorized
+//! row, the i-th lookup recorded on a row ends up in the i-th logup column. Pending lookups are written
+//! into the underlying [`LogupTraceGenerator`] on [`InteractionTraceBuilder::new_col`] and
+//! [`InteractionTraceBuilder::build`], so chips using the builder can be freely mixed with chips filling
+//! logup columns directly.
+
+use stwo_prover::{
+    constraint_framework::{
+        logup::{LogupColGenerator, LogupTraceGenerator},
+        Relation,
+    },
+    core::{
+        backend::simd::{
+            m31::{PackedBaseField, LOG_N_LANES},
+            qm31::PackedSecureField,
+            SimdBackend,
+        },
+        fields::{m31::BaseField, qm31::SecureField},
+        poly::{circle::CircleEvaluation, BitReversedOrder},
+        ColumnVec,
+    },
+};
+
+pub struct InteractionTraceBuilder {
+    logup_trace_gen: LogupTraceGenerator,
+    // Pending (numerator, denominator) pairs of every vectorized row, in the order of lookups.
+    pending: Vec<Vec<(PackedSecureField, PackedSecureField)>>,
+}
+
+impl InteractionTraceBuilder {
+    pub fn new(log_size: u32) -> Self {
+        assert!(log_size >= LOG_N_LANES);
+        Self {
+            logup_trace_gen: LogupTraceGenerator::new(log_size),
+            pending: vec![Vec::new(); 1 << (log_size - LOG_N_LANES)],
+        }
+    }
+
+    /// Adds `multiplicity / combine(values)` to the logup sum on the vectorized row `vec_row`.
+    pub fn add_lookup<R: Relation<PackedBaseField, PackedSecureField>>(
+        &mut self,
+        vec_row: usize,
+        lookup_elements: &R,
+        values: &[PackedBaseField],
+        multiplicity: impl Into<PackedSecureField>,
+    ) {
+        let denom = lookup_elements.combine(values);
+        self.pending[vec_row].push((multiplicity.into(), denom));
+    }
+
+    /// Subtracts `multiplicity / combine(values)` from the logup sum on the vectorized row `vec_row`.
+    pub fn sub_lookup<R: Relation<PackedBaseField, PackedSecureField>>(
+        &mut self,
+        vec_row: usize,
+        lookup_elements: &R,
+        values: &[PackedBaseField],
+        multiplicity: impl Into<PackedSecureField>,
+    ) {
+        let multiplicity: PackedSecureField = multiplicity.into();
+        self.add_lookup(vec_row, lookup_elements, values, -multiplicity);
+    }
+
+    /// Returns a generator of a new logup column, placed after the columns of all pending lookups.
+    pub fn new_col(&mut self) -> LogupColGenerator<'_> {
+        self.flush();
+        self.logup_trace_gen.new_col()
+    }
+
+    /// Finalizes the interaction trace, returns its columns and the claimed logup sum.
+    pub fn build(
+        mut self,
+    ) -> (
+        ColumnVec<CircleEvaluation<SimdBackend, BaseField, BitReversedOrder>>,
+        SecureField,
+    ) {
+        self.flush();
+        self.logup_trace_gen.finalize_last()
+    }
+
+    // Writes pending lookups into logup columns, every row must have the same number of lookups.
+    fn flush(&mut self) {
+        let num_cols = self.pending[0].len();
+        assert!(
+            self.pending.iter().all(|row| row.len() == num_cols),
+            "every row of the interaction trace must have the same number of lookups"
+        );
+        for col in 0..num_cols {
+            let mut logup_col_gen = self.logup_trace_gen.new_col();
+            for (vec_row, row) in self.pending.iter().enumerate() {
+                let (numerator, denom) = row[col];
+                logup_col_gen.write_frac(vec_row, numerator, denom);
+            }
+            logup_col_gen.finalize_col();
+        }
+        self.pending.iter_mut().for_each(Vec::clear);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_traits::{One, Zero};
+
+    use super::*;
+
+    stwo_prover::relation!(TestLookupElements, 2);
+
+    #[test]
+    fn test_balanced_lookups() {
+        const LOG_SIZE: u32 = LOG_N_LANES + 2;
+        let lookup_elements = TestLookupElements::dummy();
+
+        let mut builder = InteractionTraceBuilder::new(LOG_SIZE);
+        for vec_row in 0..1 << (LOG_SIZE - LOG_N_LANES) {
+            let values = [
+                PackedBaseField::broadcast(BaseField::from(vec_row as u32)),
+                PackedBaseField::broadcast(BaseField::one()),
+            ];
+            builder.add_lookup(vec_row, &lookup_elements, &values, SecureField::one());
+            builder.sub_lookup(vec_row, &lookup_elements, &values, SecureField::one());
+        }
+        let (interaction_trace, claimed_sum) = builder.build();
+
+        // Two secure columns, each occupying four base columns.
+        assert_eq!(interaction_trace.len(), 8);
+        assert_eq!(claimed_sum, SecureField::zero());
+    }
+}
@@ -1,13 +1,24 @@
+#[cfg(feature = "trace-analysis")]
+pub mod analysis;
+mod checkpoint;
+mod checks;
+#[cfg(feature = "debug-tools")]
+pub mod diff;
 pub mod eval;
+pub mod interaction;
 pub mod preprocessed;
 pub mod program;
 pub mod program_trace;
 pub mod regs;
+pub mod rows;
 pub mod sidenote;
+pub mod sparse;
 pub mod trace_builder;
 pub mod utils;
 pub mod utils_external;
 
+pub use interaction::InteractionTraceBuilder;
 pub use preprocessed::PreprocessedTraces;
 pub use program::{BoolWord, ProgramStep, Word, WordWithEffectiveBits};
-pub use trace_builder::{FinalizedTraces, TracesBuilder};
+pub use sparse::SparseTracesBuilder;
+pub use trace_builder::{FinalizedTraces, LogSizeError, MergeError, TracesBuilder};
@@ -1,13 +1,22 @@
 use nexus_common::cpu::Registers;
 use nexus_vm::{
     cpu::RegisterFile,
+    emulator::{InternalView, View},
     riscv::{BuiltinOpcode, InstructionType, Register},
     trace::{Step, Trace},
     SyscallCode, WORD_SIZE,
 };
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{program_trace::ProgramTracesBuilder, sidenote::SideNote, TracesBuilder};
+use crate::{machine::trace_log_size, traits::MachineChip};
 
 /// Program execution step.
+///
+/// With the `serde` feature, steps can be serialized and later replayed with [`replay_from_steps`] without running the VM.
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ProgramStep {
     /// Machine registers.
     pub(crate) regs: RegisterFile,
@@ -146,3 +155,119 @@ pub fn iter_program_steps<TR: Trace>(
         .chain(std::iter::repeat(None))
         .take(num_rows)
 }
+
+/// Fills the main trace of chips `C` from recorded program steps, without running the VM.
+///
+/// `view` must be the view of the execution the steps were taken from, it provides the program, the memory layout
+/// and the public input and output. The traces are sized and filled the same way as in [`crate::machine::Machine::prove`].
+/// Traces of extension components are not included.
+pub fn replay_from_steps<C: MachineChip>(steps: &[ProgramStep], view: &View) -> TracesBuilder {
+    let log_size = trace_log_size(steps.len(), view);
+
+    let mut traces = TracesBuilder::new(log_size);
+    let program_traces = ProgramTracesBuilder::new(
+        log_size,
+        view.get_program_memory(),
+        view.get_initial_memory(),
+        view.get_exit_code(),
+        view.get_public_output(),
+    );
+    let mut side_note = SideNote::new(&program_traces, view);
+    let program_steps = steps
+        .iter()
+        .cloned()
+        .map(Some)
+        .chain(std::iter::repeat(None))
+        .take(traces.num_rows());
+    for (row_idx, program_step) in program_steps.enumerate() {
+        C::fill_main_trace(&mut traces, row_idx, &program_step, &mut side_note);
+    }
+    traces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::machine::BaseComponent;
+    use nexus_vm::{
+        riscv::{BasicBlock, Instruction, Opcode},
+        trace::k_trace_direct,
+    };
+
+    fn setup_basic_block_ir() -> Vec<BasicBlock> {
+        let basic_block = BasicBlock::new(vec![
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 1, 0, 1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 2, 1, 0xfff),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SUB), 3, 1, 2),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::SLTU), 4, 3, 1),
+        ]);
+        vec![basic_block]
+    }
+
+    #[test]
+    fn test_replay_from_steps() {
+        let (view, vm_traces) =
+            k_trace_direct(&setup_basic_block_ir(), 1).expect("Failed to create trace");
+        let steps: Vec<ProgramStep> = iter_program_steps(&vm_traces, vm_traces.get_num_steps())
+            .flatten()
+            .collect();
+        assert_eq!(steps.len(), 4);
+
+        // Fill the traces from the VM trace, as the prover does.
+        let log_size = trace_log_size(vm_traces.get_num_steps(), &view);
+        let mut traces = TracesBuilder::new(log_size);
+        let program_traces = ProgramTracesBuilder::new(
+            log_size,
+            view.get_program_memory(),
+            view.get_initial_memory(),
+            view.get_exit_code(),
+            view.get_public_output(),
+        );
+        let mut side_note = SideNote::new(&program_traces, &view);
+        for (row_idx, program_step) in iter_program_steps(&vm_traces, traces.num_rows()).enumerate()
+        {
+            BaseComponent::fill_main_trace(&mut traces, row_idx, &program_step, &mut side_note);
+        }
+
+        let replayed = replay_from_steps::<BaseComponent>(&steps, &view);
+        assert_eq!(replayed.log_size, traces.log_size);
+        assert_eq!(replayed.cols, traces.cols);
+
+        #[cfg(feature = "serde")]
+        {
+            let bytes = postcard::to_allocvec(&steps).unwrap();
+            let decoded: Vec<ProgramStep> = postcard::from_bytes(&bytes).unwrap();
+
+            let replayed = replay_from_steps::<BaseComponent>(&decoded, &view);
+            assert_eq!(replayed.cols, traces.cols);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_program_step_round_trip() {
+        let mut regs = RegisterFile::default();
+        regs.write(Register::X1, 0x8000_0000);
+        regs.write(Register::X2, 42);
+        let step = Step {
+            timestamp: 7,
+            pc: 0x1000,
+            instruction: Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 3, 1, 0xfff),
+            result: Some(0x7fff_ffff),
+            ..Default::default()
+        };
+        let program_step = ProgramStep { regs, step };
+
+        let bytes = postcard::to_allocvec(&program_step).unwrap();
+        let decoded: ProgramStep = postcard::from_bytes(&bytes).unwrap();
+
+        assert_eq!(decoded.regs, program_step.regs);
+        assert_eq!(decoded.step.timestamp, program_step.step.timestamp);
+        assert_eq!(decoded.step.pc, program_step.step.pc);
+        assert_eq!(decoded.get_value_a(), program_step.get_value_a());
+        assert_eq!(decoded.get_value_b(), program_step.get_value_b());
+        assert_eq!(decoded.get_value_c(), program_step.get_value_c());
+        assert_eq!(decoded.get_result(), program_step.get_result());
+        assert_eq!(postcard::to_allocvec(&decoded).unwrap(), bytes);
+    }
+}
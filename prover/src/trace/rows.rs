@@ -0,0 +1,178 @@
+//! Row-major access to the main trace.
+
+use std::ops::{Index, IndexMut};
+
+use stwo_prover::core::fields::m31::BaseField;
+
+use super::TracesBuilder;
+use crate::column::Column;
+
+/// Immutable view of a single row of [`TracesBuilder`].
+///
+/// Single columns are accessed with `row[col]`, limbs of multi-column values with `row[(col, limb)]`.
+#[derive(Debug, Clone, Copy)]
+pub struct TraceRow<'a> {
+    traces: &'a TracesBuilder,
+    row: usize,
+}
+
+impl TraceRow<'_> {
+    /// Returns the index of the row in the trace.
+    pub fn row_idx(&self) -> usize {
+        self.row
+    }
+
+    /// Returns a copy of `N` raw columns of `col`, see [`TracesBuilder::column`].
+    pub fn column<const N: usize>(&self, col: Column) -> [BaseField; N] {
+        self.traces.column(self.row, col)
+    }
+}
+
+impl Index<Column> for TraceRow<'_> {
+    type Output = BaseField;
+
+    fn index(&self, col: Column) -> &BaseField {
+        assert_eq!(col.size(), 1, "{col:?} must be a single column");
+        &self.traces.cols[col.offset()][self.row]
+    }
+}
+
+impl Index<(Column, usize)> for TraceRow<'_> {
+    type Output = BaseField;
+
+    fn index(&self, (col, limb): (Column, usize)) -> &BaseField {
+        assert!(limb < col.size(), "{col:?} has only {} limbs", col.size());
+        &self.traces.cols[col.offset() + limb][self.row]
+    }
+}
+
+/// Mutable view of a single row of [`TracesBuilder`], holds a reference to every raw column value of the row.
+#[derive(Debug)]
+pub struct TraceRowMut<'a> {
+    values: Vec<&'a mut BaseField>,
+    row: usize,
+}
+
+impl TraceRowMut<'_> {
+    /// Returns the index of the row in the trace.
+    pub fn row_idx(&self) -> usize {
+        self.row
+    }
+
+    /// Fills columns of the row with values from BaseField slice.
+    pub fn fill_columns_base_field(&mut self, value: &[BaseField], col: Column) {
+        assert_eq!(col.size(), value.len(), "column size mismatch");
+        for (i, b) in value.iter().enumerate() {
+            *self.values[col.offset() + i] = *b;
+        }
+    }
+}
+
+impl Index<Column> for TraceRowMut<'_> {
+    type Output = BaseField;
+
+    fn index(&self, col: Column) -> &BaseField {
+        assert_eq!(col.size(), 1, "{col:?} must be a single column");
+        &self.values[col.offset()]
+    }
+}
+
+impl IndexMut<Column> for TraceRowMut<'_> {
+    fn index_mut(&mut self, col: Column) -> &mut BaseField {
+        assert_eq!(col.size(), 1, "{col:?} must be a single column");
+        &mut self.values[col.offset()]
+    }
+}
+
+impl Index<(Column, usize)> for TraceRowMut<'_> {
+    type Output = BaseField;
+
+    fn index(&self, (col, limb): (Column, usize)) -> &BaseField {
+        assert!(limb < col.size(), "{col:?} has only {} limbs", col.size());
+        &self.values[col.offset() + limb]
+    }
+}
+
+impl IndexMut<(Column, usize)> for TraceRowMut<'_> {
+    fn index_mut(&mut self, (col, limb): (Column, usize)) -> &mut BaseField {
+        assert!(limb < col.size(), "{col:?} has only {} limbs", col.size());
+        &mut self.values[col.offset() + limb]
+    }
+}
+
+impl TracesBuilder {
+    /// Returns an iterator over rows of the trace.
+    pub fn iter_rows(&self) -> impl Iterator<Item = TraceRow<'_>> {
+        (0..self.num_rows()).map(move |row| TraceRow { traces: self, row })
+    }
+
+    /// Returns an iterator over mutable rows of the trace.
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = TraceRowMut<'_>> {
+        let num_rows = self.num_rows();
+        let mut cols: Vec<_> = self.cols.iter_mut().map(|col| col.iter_mut()).collect();
+        (0..num_rows).map(move |row| TraceRowMut {
+            values: cols
+                .iter_mut()
+                .map(|col| col.next().expect("columns are shorter than the trace"))
+                .collect(),
+            row,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_traits::{One, Zero};
+
+    use super::*;
+    use crate::column::Column::{OpA, OpB, ValueA};
+
+    const LOG_SIZE: u32 = 5;
+
+    #[test]
+    fn iterate_rows() {
+        let mut traces = TracesBuilder::new(LOG_SIZE);
+        for row in 0..traces.num_rows() {
+            traces.fill_columns(row, row as u8, OpA);
+            traces.fill_columns(row, (row as u32) << 8, ValueA);
+        }
+        for (idx, row) in traces.iter_rows().enumerate() {
+            assert_eq!(row.row_idx(), idx);
+            assert_eq!(row[OpA], BaseField::from(idx as u32));
+            assert_eq!(row[(ValueA, 1)], BaseField::from(idx as u32));
+            assert_eq!(row.column::<4>(ValueA), traces.column(idx, ValueA));
+        }
+    }
+
+    #[test]
+    fn mutations_survive_finalize() {
+        let mut traces = TracesBuilder::new(LOG_SIZE);
+        let mut expected = TracesBuilder::new(LOG_SIZE);
+        for (idx, mut row) in traces.rows_mut().enumerate() {
+            row[OpA] = BaseField::from(idx as u32);
+            row[(ValueA, 3)] = BaseField::one();
+            let doubled = row[OpA] + row[OpA];
+            row.fill_columns_base_field(&[doubled], OpB);
+        }
+        for row in 0..expected.num_rows() {
+            expected.fill_columns(row, row as u8, OpA);
+            expected.fill_columns(row, 1u32 << 24, ValueA);
+            expected.fill_columns(row, BaseField::from(2 * row as u32), OpB);
+        }
+        assert!(traces.iter_rows().all(|row| row[(ValueA, 0)].is_zero()));
+
+        let traces = traces.finalize().into_circle_evaluation();
+        let expected = expected.finalize().into_circle_evaluation();
+        for (col, expected_col) in traces.iter().zip(&expected) {
+            assert_eq!(col.values.as_slice(), expected_col.values.as_slice());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "must be a single column")]
+    fn index_word_column() {
+        let traces = TracesBuilder::new(LOG_SIZE);
+        let row = traces.iter_rows().next().unwrap();
+        assert!(row[ValueA].is_zero());
+    }
+}
@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use num_traits::Zero;
+use stwo_prover::core::{backend::simd::m31::LOG_N_LANES, fields::m31::BaseField};
+
+use super::{
+    trace_builder::{FinalizedTraces, TracesBuilder},
+    utils::IntoBaseFields,
+};
+use crate::column::Column;
+
+/// Main trace builder that only stores non-zero values.
+///
+/// Most instruction-specific columns are zero on every row executing a different opcode, this builder
+/// keeps such columns as row-to-value maps and materializes dense columns only when the trace is finalized.
+/// The API mirrors [`TracesBuilder`].
+#[derive(Debug, Clone)]
+pub struct SparseTracesBuilder {
+    cols: Vec<HashMap<usize, BaseField>>,
+    log_size: u32,
+}
+
+impl SparseTracesBuilder {
+    /// Returns [`Column::COLUMNS_NUM`] empty columns, each one `2.pow(log_size)` in length.
+    pub fn new(log_size: u32) -> Self {
+        assert!(log_size >= LOG_N_LANES);
+        Self {
+            cols: vec![HashMap::new(); Column::COLUMNS_NUM],
+            log_size,
+        }
+    }
+
+    /// Returns the log_size of columns.
+    pub fn log_size(&self) -> u32 {
+        self.log_size
+    }
+
+    /// Returns the number of rows
+    pub fn num_rows(&self) -> usize {
+        1 << self.log_size
+    }
+
+    /// Returns the number of stored non-zero values across all columns.
+    pub fn num_non_zero(&self) -> usize {
+        self.cols.iter().map(HashMap::len).sum()
+    }
+
+    /// Returns a copy of `N` raw columns in range `[offset..offset + N]` at `row`, where
+    /// `N` is assumed to be equal `Column::size` of a `col`.
+    pub fn column<const N: usize>(&self, row: usize, col: Column) -> [BaseField; N] {
+        assert_eq!(col.size(), N, "column size mismatch");
+        assert!(row < self.num_rows(), "row index out of bounds");
+
+        let offset = col.offset();
+        std::array::from_fn(|i| {
+            self.cols[offset + i]
+                .get(&row)
+                .copied()
+                .unwrap_or_else(BaseField::zero)
+        })
+    }
+
+    /// Fills columns with values of type `T`, see [`TracesBuilder::fill_columns`].
+    pub(crate) fn fill_columns<const N: usize, T: IntoBaseFields<N>>(
+        &mut self,
+        row: usize,
+        value: T,
+        col: Column,
+    ) {
+        let base_field_values = value.into_base_fields();
+        self.fill_columns_base_field(row, &base_field_values, col);
+    }
+
+    /// Fills columns with values from a byte slice.
+    pub fn fill_columns_bytes(&mut self, row: usize, value: &[u8], col: Column) {
+        let base_field_values = value
+            .iter()
+            .map(|b| BaseField::from(*b as u32))
+            .collect_vec();
+        self.fill_columns_base_field(row, base_field_values.as_slice(), col);
+    }
+
+    /// Fills columns with values from BaseField slice.
+    ///
+    /// Zero values are removed from the storage instead of being written.
+    pub fn fill_columns_base_field(&mut self, row: usize, value: &[BaseField], col: Column) {
+        assert_eq!(col.size(), value.len(), "column size mismatch");
+        assert!(row < self.num_rows(), "row index out of bounds");
+        for (i, b) in value.iter().enumerate() {
+            let values = &mut self.cols[col.offset() + i];
+            if b.is_zero() {
+                values.remove(&row);
+            } else {
+                values.insert(row, *b);
+            }
+        }
+    }
+
+    /// Converts the trace into dense columns, missing entries are zero-filled.
+    pub fn into_dense(self) -> TracesBuilder {
+        let mut traces = TracesBuilder::new(self.log_size);
+        for (dense, sparse) in traces.cols.iter_mut().zip(self.cols) {
+            for (row, value) in sparse {
+                dense[row] = value;
+            }
+        }
+        traces
+    }
+
+    /// Materializes dense columns and finalizes them, see [`TracesBuilder::finalize`].
+    pub fn finalize(self) -> FinalizedTraces {
+        self.into_dense().finalize()
+    }
+}
+
+impl From<&TracesBuilder> for SparseTracesBuilder {
+    fn from(traces: &TracesBuilder) -> Self {
+        let cols = traces
+            .cols
+            .iter()
+            .map(|col| {
+                col.iter()
+                    .enumerate()
+                    .filter(|(_, value)| !value.is_zero())
+                    .map(|(row, value)| (row, *value))
+                    .collect()
+            })
+            .collect();
+        Self {
+            cols,
+            log_size: traces.log_size,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::column::Column::{OpA, ValueA, ValueB};
+
+    const LOG_SIZE: u32 = 6;
+
+    #[test]
+    fn sparse_matches_dense() {
+        let mut dense = TracesBuilder::new(LOG_SIZE);
+        let mut sparse = SparseTracesBuilder::new(LOG_SIZE);
+        for row in (0..dense.num_rows()).step_by(3) {
+            let word = ((row as u32) << 16) | 1;
+            dense.fill_columns(row, word, ValueA);
+            sparse.fill_columns(row, word, ValueA);
+            dense.fill_columns(row, true, OpA);
+            sparse.fill_columns(row, true, OpA);
+        }
+        // overwriting with zero removes the entry
+        sparse.fill_columns(0, 5u32, ValueB);
+        sparse.fill_columns(0, 0u32, ValueB);
+
+        for row in 0..dense.num_rows() {
+            assert_eq!(
+                sparse.column::<4>(row, ValueA),
+                dense.column::<4>(row, ValueA)
+            );
+            assert_eq!(sparse.column::<1>(row, OpA), dense.column::<1>(row, OpA));
+        }
+        // rows divisible by 3: two non-zero limbs of ValueA (row 0 only has one) and OpA
+        let num_filled = dense.num_rows().div_ceil(3);
+        assert_eq!(sparse.num_non_zero(), 3 * num_filled - 1);
+
+        let from_dense = SparseTracesBuilder::from(&dense);
+        assert_eq!(from_dense.num_non_zero(), sparse.num_non_zero());
+
+        let dense = dense.finalize().into_circle_evaluation();
+        let sparse = sparse.finalize().into_circle_evaluation();
+        for (dense, sparse) in dense.iter().zip(&sparse) {
+            assert_eq!(dense.values.as_slice(), sparse.values.as_slice());
+        }
+    }
+}
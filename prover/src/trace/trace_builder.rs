@@ -1,6 +1,9 @@
+use std::{cmp::Ordering, fmt, ops::Range};
+
 use itertools::Itertools;
 use nexus_vm::WORD_SIZE;
 use num_traits::Zero;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use stwo_prover::core::{
     backend::simd::{column::BaseColumn, m31::LOG_N_LANES, SimdBackend},
     fields::m31::BaseField,
@@ -11,8 +14,8 @@ use stwo_prover::core::{
     ColumnVec,
 };
 
-use super::utils::{finalize_columns, IntoBaseFields};
-use crate::column::Column;
+use super::utils::{finalize_columns, FromBaseFields, IntoBaseFields};
+use crate::{column::Column, traits::MachineChip};
 
 /// Main ([`stwo_prover::constraint_framework::ORIGINAL_TRACE_IDX`]) trace builder which implements
 /// mutable access to columns.
@@ -34,6 +37,111 @@ impl TracesBuilder {
         }
     }
 
+    /// Returns zeroed columns sized to fit `num_steps` program steps executed by the chip `C`.
+    pub fn new_for_chip<C: MachineChip>(num_steps: usize) -> Self {
+        let log_size = C::required_log_size(num_steps * C::max_rows_per_step());
+        assert!(log_size >= LOG_N_LANES);
+        Self::new(log_size)
+    }
+
+    /// Zero-extends every column to `2.pow(new_log_size)` rows.
+    ///
+    /// Returns an error if `new_log_size` is smaller than the current log size, the trace is left unchanged then.
+    pub fn extend_to_log_size(&mut self, new_log_size: u32) -> Result<(), LogSizeError> {
+        if new_log_size < self.log_size {
+            return Err(LogSizeError {
+                log_size: self.log_size,
+                requested: new_log_size,
+            });
+        }
+        for col in &mut self.cols {
+            col.resize(1 << new_log_size, BaseField::zero());
+        }
+        self.log_size = new_log_size;
+        Ok(())
+    }
+
+    /// Returns the index of the last row with a non-zero value in any column plus one, or zero for an empty trace.
+    pub fn used_row_count(&self) -> usize {
+        self.cols
+            .iter()
+            .filter_map(|col| col.iter().rposition(|value| !value.is_zero()))
+            .max()
+            .map_or(0, |last_row| last_row + 1)
+    }
+
+    /// Truncates the trace to the smallest log size that keeps all non-zero rows, but no less than [`LOG_N_LANES`].
+    ///
+    /// Columns are stored in coset order and only reordered by [`Self::finalize`], so truncation doesn't require
+    /// any reordering. Constraints that relate the last row to the first one must still hold on the trimmed trace.
+    pub fn shrink_to_fit(&mut self) {
+        let log_size = self
+            .used_row_count()
+            .next_power_of_two()
+            .ilog2()
+            .max(LOG_N_LANES);
+        for col in &mut self.cols {
+            col.truncate(1 << log_size);
+            col.shrink_to_fit();
+        }
+        self.log_size = log_size;
+    }
+
+    /// Returns a copy of rows `range` of the trace, the `i`-th row of the copy is the `range.start + i`-th row of
+    /// `self`.
+    ///
+    /// The copy has the smallest log size that fits the range, but no less than [`LOG_N_LANES`], remaining rows are
+    /// zero. It is meant for inspecting a part of a large trace, constraints spanning the whole domain don't hold
+    /// on the copy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds of the trace.
+    pub fn select_rows(&self, range: Range<usize>) -> Self {
+        assert!(
+            range.start <= range.end && range.end <= self.num_rows(),
+            "rows {range:?} are out of bounds of a trace with {} rows",
+            self.num_rows()
+        );
+        let log_size = range.len().next_power_of_two().ilog2().max(LOG_N_LANES);
+        let cols = self
+            .cols
+            .iter()
+            .map(|col| {
+                let mut rows = col[range.clone()].to_vec();
+                rows.resize(1 << log_size, BaseField::zero());
+                rows
+            })
+            .collect();
+        Self { cols, log_size }
+    }
+
+    /// Moves columns `cols` of `other` into `self`, used to combine traces filled in separate passes.
+    ///
+    /// Returns an error if any of the destination columns has a non-zero value, which indicates that both
+    /// passes wrote to it. The trace is left unchanged then.
+    ///
+    /// # Panics
+    ///
+    /// Panics if log sizes of the traces differ.
+    pub fn merge(&mut self, mut other: TracesBuilder, cols: &[Column]) -> Result<(), MergeError> {
+        assert_eq!(self.log_size, other.log_size, "log size mismatch");
+
+        for &col in cols {
+            for idx in col.offset()..col.offset() + col.size() {
+                if let Some(row) = self.cols[idx].iter().position(|value| !value.is_zero()) {
+                    return Err(MergeError { col, row });
+                }
+            }
+        }
+        for &col in cols {
+            for idx in col.offset()..col.offset() + col.size() {
+                self.cols[idx] = std::mem::take(&mut other.cols[idx]);
+            }
+        }
+        Ok(())
+    }
+
     /// Returns inner representation of columns.
     pub fn into_inner(self) -> Vec<Vec<BaseField>> {
         self.cols
@@ -59,6 +167,15 @@ impl TracesBuilder {
         std::array::from_fn(|_idx| iter.next().expect("invalid offset; must be unreachable")[row])
     }
 
+    /// Returns the value of a word column at `row`, assembled from little-endian bytes, see
+    /// [`Self::fill_columns_from_u32`].
+    ///
+    /// Panics if `col` isn't a word column or contains a value that isn't a byte.
+    pub fn column_u32(&self, row: usize, col: Column) -> u32 {
+        assert_eq!(col.size(), WORD_SIZE, "column size mismatch");
+        u32::from_base_fields(self.column(row, col))
+    }
+
     /// Returns mutable reference to `N` raw columns in range `[offset..offset + N]` at `row`,
     /// where `N` is assumed to be equal `Column::size` of a `col`.
     pub fn column_mut<const N: usize>(&mut self, row: usize, col: Column) -> [&mut BaseField; N] {
@@ -82,6 +199,26 @@ impl TracesBuilder {
         self.fill_columns_base_field(row, &base_field_values, col);
     }
 
+    /// Fills a word column with `value` split into little-endian bytes.
+    pub fn fill_columns_from_u32(&mut self, row: usize, value: u32, col: Column) {
+        assert_eq!(col.size(), WORD_SIZE, "column size mismatch");
+        self.fill_columns(row, value, col);
+    }
+
+    /// Fills `N` consecutive words of `col` with `values`, each one split into little-endian bytes.
+    pub fn fill_columns_from_u32_array<const N: usize>(
+        &mut self,
+        row: usize,
+        values: [u32; N],
+        col: Column,
+    ) {
+        let base_field_values = values
+            .into_iter()
+            .flat_map(|value| value.into_base_fields())
+            .collect_vec();
+        self.fill_columns_base_field(row, &base_field_values, col);
+    }
+
     /// Fills columns with values from a byte slice.
     pub fn fill_columns_bytes(&mut self, row: usize, value: &[u8], col: Column) {
         let base_field_values = value
@@ -100,6 +237,37 @@ impl TracesBuilder {
         }
     }
 
+    /// Calls `f` on every row of the trace in parallel.
+    ///
+    /// Rows are split into contiguous chunks, each one a multiple of `2.pow(LOG_N_LANES)` in length, and every
+    /// thread receives a [`RowsChunkMut`] with disjoint mutable access to its rows of all columns. Columns are
+    /// zeroed on creation, so rows that `f` leaves untouched remain zero.
+    pub fn fill_rows_parallel<F>(&mut self, f: F)
+    where
+        F: Fn(usize, &mut RowsChunkMut<'_>) + Sync,
+    {
+        let num_rows = self.num_rows();
+        let chunk_size = (num_rows / rayon::current_num_threads())
+            .next_power_of_two()
+            .max(1 << LOG_N_LANES);
+        let mut chunks: Vec<RowsChunkMut<'_>> = (0..num_rows / chunk_size)
+            .map(|i| RowsChunkMut {
+                first_row: i * chunk_size,
+                cols: Vec::with_capacity(self.cols.len()),
+            })
+            .collect();
+        for col in self.cols.iter_mut() {
+            for (chunk, rows) in chunks.iter_mut().zip(col.chunks_mut(chunk_size)) {
+                chunk.cols.push(rows);
+            }
+        }
+        chunks.into_par_iter().for_each(|mut chunk| {
+            for row in chunk.rows() {
+                f(row, &mut chunk);
+            }
+        });
+    }
+
     /// Fills columns with values from a byte slice, applying a selector.
     ///
     /// If the selector is true, fills the columns with values from the byte slice. Otherwise, fills with zeros.
@@ -127,6 +295,44 @@ impl TracesBuilder {
         }
     }
 
+    /// Returns a copy of the trace with rows sorted lexicographically by the values of `sort_keys`.
+    ///
+    /// Multi-limb keys are compared starting from the most significant (last) limb, so that little-endian
+    /// words are ordered numerically. The sort is stable, rows with equal keys keep their relative order.
+    pub fn build_sorted_trace(&self, sort_keys: &[Column]) -> Self {
+        let key_cols: Vec<&[BaseField]> = sort_keys
+            .iter()
+            .flat_map(|col| {
+                (col.offset()..col.offset() + col.size())
+                    .rev()
+                    .map(|idx| self.cols[idx].as_slice())
+            })
+            .collect();
+        let mut rows: Vec<usize> = (0..self.num_rows()).collect();
+        rows.sort_by(|&a, &b| {
+            key_cols
+                .iter()
+                .map(|col| col[a].0.cmp(&col[b].0))
+                .find(|ord| ord.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+        self.permute_rows(&rows)
+    }
+
+    /// Returns a copy of the trace whose `i`-th row is the `rows[i]`-th row of `self`.
+    fn permute_rows(&self, rows: &[usize]) -> Self {
+        assert_eq!(rows.len(), self.num_rows(), "permutation length mismatch");
+        let cols = self
+            .cols
+            .iter()
+            .map(|col| rows.iter().map(|&row| col[row]).collect())
+            .collect();
+        Self {
+            cols,
+            log_size: self.log_size,
+        }
+    }
+
     /// Finalize trace and convert raw columns to [`BaseColumn`].
     pub fn finalize(self) -> FinalizedTraces {
         let cols = finalize_columns(self.cols);
@@ -138,6 +344,88 @@ impl TracesBuilder {
     }
 }
 
+/// Error returned by [`TracesBuilder::extend_to_log_size`] on an attempt to shrink the trace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogSizeError {
+    pub log_size: u32,
+    pub requested: u32,
+}
+
+impl fmt::Display for LogSizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cannot extend trace of log size {} to smaller log size {}",
+            self.log_size, self.requested
+        )
+    }
+}
+
+impl std::error::Error for LogSizeError {}
+
+/// Error returned by [`TracesBuilder::merge`] if a destination column is already filled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MergeError {
+    pub col: Column,
+    /// First row with a non-zero value.
+    pub row: usize,
+}
+
+impl fmt::Display for MergeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cannot merge into non-empty column {:?}, row {} is already filled",
+            self.col, self.row
+        )
+    }
+}
+
+impl std::error::Error for MergeError {}
+
+/// Mutable access to a contiguous range of rows of [`TracesBuilder`], see [`TracesBuilder::fill_rows_parallel`].
+///
+/// Rows are addressed by their index in the whole trace.
+pub struct RowsChunkMut<'a> {
+    first_row: usize,
+    cols: Vec<&'a mut [BaseField]>,
+}
+
+impl RowsChunkMut<'_> {
+    /// Returns the range of trace rows accessible through this chunk.
+    pub fn rows(&self) -> Range<usize> {
+        let len = self.cols.first().map_or(0, |col| col.len());
+        self.first_row..self.first_row + len
+    }
+
+    /// Returns a copy of `N` raw columns in range `[offset..offset + N]` at `row`.
+    pub fn column<const N: usize>(&self, row: usize, col: Column) -> [BaseField; N] {
+        assert_eq!(col.size(), N, "column size mismatch");
+        let row = row - self.first_row;
+        std::array::from_fn(|i| self.cols[col.offset() + i][row])
+    }
+
+    /// Fills columns of `row` with `value`, same as [`TracesBuilder::fill_columns`].
+    pub(crate) fn fill_columns<const N: usize, T: IntoBaseFields<N>>(
+        &mut self,
+        row: usize,
+        value: T,
+        col: Column,
+    ) {
+        let base_field_values = value.into_base_fields();
+        self.fill_columns_base_field(row, &base_field_values, col);
+    }
+
+    /// Fills columns of `row` with values from BaseField slice.
+    pub fn fill_columns_base_field(&mut self, row: usize, value: &[BaseField], col: Column) {
+        assert_eq!(col.size(), value.len(), "column size mismatch");
+        let row = row - self.first_row;
+        for (i, b) in value.iter().enumerate() {
+            self.cols[col.offset() + i][row] = *b;
+        }
+    }
+}
+
 /// Finalized main trace that stores columns in (bit reversed) circle domain order.
 #[derive(Debug, Clone)]
 pub struct FinalizedTraces {
@@ -165,3 +453,217 @@ impl FinalizedTraces {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        chips::{AddChip, CpuChip},
+        column::Column::{OpA, OpB, ValueA},
+    };
+
+    const LOG_SIZE: u32 = 5;
+
+    #[test]
+    fn traces_sized_for_chip() {
+        assert_eq!(
+            TracesBuilder::new_for_chip::<AddChip>(0).log_size(),
+            LOG_N_LANES
+        );
+        assert_eq!(
+            TracesBuilder::new_for_chip::<AddChip>(1).log_size(),
+            LOG_N_LANES
+        );
+        assert_eq!(
+            TracesBuilder::new_for_chip::<(CpuChip, AddChip)>(17).log_size(),
+            5
+        );
+        assert_eq!(
+            TracesBuilder::new_for_chip::<CpuChip>(1 << 10).num_rows(),
+            1 << 10
+        );
+    }
+
+    #[test]
+    fn parallel_fill_matches_sequential() {
+        let fill = |row: usize| ((row as u32).wrapping_mul(0x01010101), (row % 7) as u8);
+
+        let mut sequential = TracesBuilder::new(LOG_SIZE + 4);
+        for row in 0..sequential.num_rows() {
+            let (word, byte) = fill(row);
+            sequential.fill_columns(row, word, ValueA);
+            sequential.fill_columns(row, byte, OpA);
+        }
+        let mut parallel = TracesBuilder::new(LOG_SIZE + 4);
+        parallel.fill_rows_parallel(|row, chunk| {
+            let (word, byte) = fill(row);
+            chunk.fill_columns(row, word, ValueA);
+            chunk.fill_columns(row, byte, OpA);
+            assert_eq!(chunk.column::<1>(row, OpA), [BaseField::from(byte as u32)]);
+        });
+        assert_eq!(parallel.cols, sequential.cols);
+    }
+
+    #[test]
+    fn fill_words() {
+        let mut traces = TracesBuilder::new(LOG_SIZE);
+        traces.fill_columns_from_u32(1, 0x04030201, ValueA);
+        assert_eq!(
+            traces.column::<4>(1, ValueA),
+            [1u32, 2, 3, 4].map(BaseField::from)
+        );
+
+        traces.fill_columns_from_u32_array(2, [0x04030201], ValueA);
+        assert_eq!(traces.column::<4>(2, ValueA), traces.column::<4>(1, ValueA));
+
+        for value in [0, 0xff, 0x0100, 0x8000_0000, u32::MAX] {
+            traces.fill_columns_from_u32(3, value, ValueA);
+            assert_eq!(traces.column_u32(3, ValueA), value);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "column size mismatch")]
+    fn read_word_from_byte_column() {
+        let traces = TracesBuilder::new(LOG_SIZE);
+        traces.column_u32(0, OpA);
+    }
+
+    #[test]
+    #[should_panic(expected = "column size mismatch")]
+    fn fill_word_into_byte_column() {
+        let mut traces = TracesBuilder::new(LOG_SIZE);
+        traces.fill_columns_from_u32_array(0, [1, 2], ValueA);
+    }
+
+    #[test]
+    fn select_rows_copies_range() {
+        let mut traces = TracesBuilder::new(LOG_SIZE + 2);
+        for row in 0..traces.num_rows() {
+            traces.fill_columns(row, row as u32 * 3, ValueA);
+            traces.fill_columns(row, (row % 5) as u8, OpB);
+        }
+
+        let all = traces.select_rows(0..traces.num_rows());
+        assert_eq!(all.log_size(), traces.log_size());
+        assert_eq!(all.cols, traces.cols);
+
+        let window = traces.select_rows(90..110);
+        assert_eq!(window.log_size(), LOG_SIZE);
+        for row in 0..20 {
+            assert_eq!(
+                window.column::<4>(row, ValueA),
+                traces.column::<4>(90 + row, ValueA)
+            );
+            assert_eq!(
+                window.column::<1>(row, OpB),
+                traces.column::<1>(90 + row, OpB)
+            );
+        }
+        assert!(window.iter_rows().skip(20).all(|row| row[OpB].is_zero()));
+        assert_eq!(traces.select_rows(3..3).log_size(), LOG_N_LANES);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn select_rows_out_of_bounds() {
+        let traces = TracesBuilder::new(LOG_SIZE);
+        traces.select_rows(0..(1 << LOG_SIZE) + 1);
+    }
+
+    #[test]
+    fn extend_and_shrink() {
+        let mut traces = TracesBuilder::new(LOG_SIZE);
+        traces.fill_columns(3, 0xff00u32, ValueA);
+        traces.fill_columns(17, 1u8, OpA);
+
+        assert_eq!(
+            traces.extend_to_log_size(LOG_SIZE - 1),
+            Err(LogSizeError {
+                log_size: LOG_SIZE,
+                requested: LOG_SIZE - 1
+            })
+        );
+        traces.extend_to_log_size(LOG_SIZE + 3).unwrap();
+        assert_eq!(traces.log_size(), LOG_SIZE + 3);
+        assert!(traces.cols.iter().all(|col| col.len() == traces.num_rows()));
+        assert_eq!(traces.column::<4>(3, ValueA), 0xff00u32.into_base_fields());
+        assert_eq!(traces.column::<1>(17, OpA), [BaseField::from(1u32)]);
+
+        // last non-zero row is 17, which requires 32 rows
+        assert_eq!(traces.used_row_count(), 18);
+        traces.shrink_to_fit();
+        assert_eq!(traces.log_size(), 5);
+        assert!(traces.cols.iter().all(|col| col.len() == 32));
+        assert_eq!(traces.column::<1>(17, OpA), [BaseField::from(1u32)]);
+
+        let mut empty = TracesBuilder::new(LOG_SIZE + 2);
+        assert_eq!(empty.used_row_count(), 0);
+        empty.shrink_to_fit();
+        assert_eq!(empty.log_size(), LOG_N_LANES);
+    }
+
+    #[test]
+    fn merge_disjoint_passes() {
+        let fill_first = |traces: &mut TracesBuilder, row: usize| {
+            traces.fill_columns(row, (row as u32).wrapping_mul(0x01010101), ValueA)
+        };
+        let fill_second =
+            |traces: &mut TracesBuilder, row: usize| traces.fill_columns(row, (row % 5) as u8, OpA);
+
+        let mut single_pass = TracesBuilder::new(LOG_SIZE);
+        let mut first_pass = TracesBuilder::new(LOG_SIZE);
+        let mut second_pass = TracesBuilder::new(LOG_SIZE);
+        for row in 0..single_pass.num_rows() {
+            fill_first(&mut single_pass, row);
+            fill_second(&mut single_pass, row);
+            fill_first(&mut first_pass, row);
+            fill_second(&mut second_pass, row);
+        }
+
+        first_pass.merge(second_pass.clone(), &[OpA]).unwrap();
+        assert_eq!(first_pass.cols, single_pass.cols);
+
+        assert_eq!(
+            first_pass.merge(second_pass, &[OpB, OpA]),
+            Err(MergeError { col: OpA, row: 1 })
+        );
+        assert_eq!(first_pass.cols, single_pass.cols);
+    }
+
+    #[test]
+    fn sorted_trace_orders_rows_by_keys() {
+        let mut traces = TracesBuilder::new(LOG_SIZE);
+        let num_rows = traces.num_rows();
+        for row in 0..num_rows {
+            // descending primary key with duplicates, the original row index as payload
+            traces.fill_columns(row, ((num_rows - row) / 2) as u8, OpA);
+            traces.fill_columns(row, row as u8, OpB);
+            // least significant limb decreases, while the word itself increases
+            let word = (num_rows - 1 - row) as u32 | ((row as u32) << 8);
+            traces.fill_columns(row, word, ValueA);
+        }
+
+        let sorted = traces.build_sorted_trace(&[OpA]);
+        assert_eq!(sorted.log_size(), traces.log_size());
+        assert_eq!(sorted.cols.len(), Column::COLUMNS_NUM);
+        for row in 1..num_rows {
+            let [prev_key] = sorted.column(row - 1, OpA);
+            let [key] = sorted.column(row, OpA);
+            assert!(prev_key.0 <= key.0);
+            if prev_key == key {
+                // stable sort
+                let [prev] = sorted.column(row - 1, OpB);
+                let [curr] = sorted.column(row, OpB);
+                assert!(prev.0 < curr.0);
+            }
+        }
+
+        // word columns are compared numerically, i.e. starting from the most significant limb
+        let sorted = traces.build_sorted_trace(&[ValueA]);
+        for row in 0..num_rows {
+            let [payload] = sorted.column(row, OpB);
+            assert_eq!(payload, BaseField::from(row as u32));
+        }
+    }
+}
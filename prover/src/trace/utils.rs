@@ -1,7 +1,13 @@
+use std::simd::{cmp::SimdOrd, u32x16, Simd};
+
 use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
 use stwo_prover::core::{
-    backend::simd::{column::BaseColumn, SimdBackend},
-    fields::m31::BaseField,
+    backend::simd::{
+        column::BaseColumn,
+        m31::{PackedBaseField, N_LANES},
+        SimdBackend,
+    },
+    fields::m31::{BaseField, P},
 };
 
 use nexus_vm::WORD_SIZE;
@@ -105,6 +111,38 @@ pub fn finalize_columns(columns: Vec<Vec<BaseField>>) -> Vec<BaseColumn> {
     ret
 }
 
+/// Converts `u32` values into a [`BaseColumn`] in the same order, reducing them modulo `P`.
+///
+/// Equivalent to `BaseColumn::from_iter(data.iter().map(|&v| BaseField::from(v)))`, but loads and
+/// reduces [`N_LANES`] values at a time. The tail shorter than a vector is converted element-wise.
+pub fn fill_base_column_from_u32s(data: &[u32]) -> BaseColumn {
+    let modulus = u32x16::splat(P);
+    let chunks = data.chunks_exact(N_LANES);
+    let tail = chunks.remainder();
+
+    let mut packed: Vec<PackedBaseField> = Vec::with_capacity(data.len().div_ceil(N_LANES));
+    packed.extend(chunks.map(|chunk| {
+        let values = Simd::from_slice(chunk);
+        // 2^31 = 1 (mod P), the sum is in [0, P], and P itself is mapped to zero.
+        let sum = (values & modulus) + (values >> 31);
+        let reduced = sum.simd_min(sum - modulus);
+        // Safety: reduced values are in [0, P).
+        unsafe { PackedBaseField::from_simd_unchecked(reduced) }
+    }));
+    if !tail.is_empty() {
+        let mut last = [BaseField::from(0u32); N_LANES];
+        for (dst, &src) in last.iter_mut().zip(tail) {
+            *dst = BaseField::from(src);
+        }
+        packed.push(PackedBaseField::from_array(last));
+    }
+
+    BaseColumn {
+        data: packed,
+        length: data.len(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -126,4 +164,19 @@ mod tests {
             assert_eq!(reordered, &vals[idx]);
         }
     }
+
+    #[test]
+    fn test_fill_base_column_from_u32s() {
+        let data: Vec<u32> = [0, 1, P - 1, P, P + 1, u32::MAX]
+            .into_iter()
+            .chain((0..40u32).map(|i| i.wrapping_mul(0x9E37_79B9)))
+            .collect();
+
+        for len in [0, 5, N_LANES, 2 * N_LANES + 3, data.len()] {
+            let col = fill_base_column_from_u32s(&data[..len]);
+            let expected = BaseColumn::from_iter(data[..len].iter().map(|&v| BaseField::from(v)));
+            assert_eq!(col.length, len);
+            assert_eq!(col.as_slice(), expected.as_slice());
+        }
+    }
 }
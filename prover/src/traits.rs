@@ -1,10 +1,12 @@
+use std::fmt;
+
 use impl_trait_for_tuples::impl_for_tuples;
 
 use num_traits::Zero;
 use stwo_prover::{
-    constraint_framework::{logup::LogupTraceGenerator, EvalAtRow},
+    constraint_framework::EvalAtRow,
     core::{
-        backend::simd::SimdBackend,
+        backend::simd::{m31::LOG_N_LANES, SimdBackend},
         channel::Channel,
         fields::{m31::BaseField, qm31::SecureField},
         poly::{circle::CircleEvaluation, BitReversedOrder},
@@ -13,10 +15,11 @@ use stwo_prover::{
 };
 
 use crate::{
+    column::Column,
     components::AllLookupElements,
     trace::{
         eval::TraceEval, preprocessed::PreprocessedTraces, program_trace::ProgramTraces,
-        sidenote::SideNote, FinalizedTraces, ProgramStep, TracesBuilder,
+        sidenote::SideNote, FinalizedTraces, InteractionTraceBuilder, ProgramStep, TracesBuilder,
     },
 };
 
@@ -48,8 +51,9 @@ pub trait MachineChip {
     ///
     /// The signature of this method is intentionally similar to `gen_interaction_trace()` in stwo examples.
     /// This method isn't called row-by-row because stwo logup library fills 16 rows of the interaction trace at a time.
+    /// Lookups are usually recorded with [`InteractionTraceBuilder::add_lookup`] and [`InteractionTraceBuilder::sub_lookup`].
     fn fill_interaction_trace(
-        _logup_trace_gen: &mut LogupTraceGenerator,
+        _interaction_trace: &mut InteractionTraceBuilder,
         _original_traces: &FinalizedTraces,
         _preprocessed_trace: &PreprocessedTraces,
         _program_traces: &ProgramTraces,
@@ -73,6 +77,72 @@ pub trait MachineChip {
     /// }
     /// ```
     fn draw_lookup_elements(_: &mut AllLookupElements, _: &mut impl Channel) {}
+
+    /// Maximum number of main trace rows the chip fills for a single program step.
+    fn max_rows_per_step() -> usize {
+        1
+    }
+
+    /// Returns the smallest log size of a trace that fits `num_rows` rows, no less than [`LOG_N_LANES`].
+    fn required_log_size(num_rows: usize) -> u32 {
+        num_rows.next_power_of_two().ilog2().max(LOG_N_LANES)
+    }
+
+    /// Main trace columns read by the constraints of the chip.
+    ///
+    /// Only used by tests to evaluate the chip on a trace with all other columns zeroed out, empty if the chip
+    /// doesn't declare its columns.
+    fn owned_columns() -> Vec<Column> {
+        Vec::new()
+    }
+
+    /// Cheap consistency checks of the filled main trace, e.g. that byte columns only contain bytes.
+    ///
+    /// Called by tests before evaluating constraints, a chip that fails these checks will fail its constraints as
+    /// well. Doesn't replace constraints and is never called by the prover.
+    fn validate_witness(_traces: &TracesBuilder) -> Result<(), WitnessError> {
+        Ok(())
+    }
+}
+
+/// A main trace cell violating [`MachineChip::validate_witness`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WitnessError {
+    pub row: usize,
+    pub description: String,
+}
+
+impl fmt::Display for WitnessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "row {}: {}", self.row, self.description)
+    }
+}
+
+impl std::error::Error for WitnessError {}
+
+/// Checks that every cell of `cols` is at most `max_value`.
+pub(crate) fn check_columns_bounded(
+    traces: &TracesBuilder,
+    cols: &[Column],
+    max_value: u32,
+) -> Result<(), WitnessError> {
+    for &col in cols {
+        for (limb_idx, values) in traces.cols[col.offset()..col.offset() + col.size()]
+            .iter()
+            .enumerate()
+        {
+            if let Some(row) = values.iter().position(|value| value.0 > max_value) {
+                return Err(WitnessError {
+                    row,
+                    description: format!(
+                        "{col:?}[{limb_idx}] = {} exceeds {max_value}",
+                        values[row].0
+                    ),
+                });
+            }
+        }
+    }
+    Ok(())
 }
 
 #[impl_for_tuples(1, 26)]
@@ -95,18 +165,35 @@ impl MachineChip for Tuple {
     }
 
     fn fill_interaction_trace(
-        logup_trace_gen: &mut LogupTraceGenerator,
+        interaction_trace: &mut InteractionTraceBuilder,
         original_traces: &FinalizedTraces,
         preprocessed_traces: &PreprocessedTraces,
         program_traces: &ProgramTraces,
         lookup_elements: &AllLookupElements,
     ) {
-        for_tuples!( #( Tuple::fill_interaction_trace(logup_trace_gen, original_traces, preprocessed_traces, program_traces, lookup_elements); )* );
+        for_tuples!( #( Tuple::fill_interaction_trace(interaction_trace, original_traces, preprocessed_traces, program_traces, lookup_elements); )* );
     }
 
     fn draw_lookup_elements(all_elements: &mut AllLookupElements, channel: &mut impl Channel) {
         for_tuples!( #( Tuple::draw_lookup_elements(all_elements, channel); )* );
     }
+
+    fn max_rows_per_step() -> usize {
+        let mut max_rows = 0;
+        for_tuples!( #( max_rows = max_rows.max(Tuple::max_rows_per_step()); )* );
+        max_rows
+    }
+
+    fn owned_columns() -> Vec<Column> {
+        let mut columns = Vec::new();
+        for_tuples!( #( columns.extend(Tuple::owned_columns()); )* );
+        columns
+    }
+
+    fn validate_witness(traces: &TracesBuilder) -> Result<(), WitnessError> {
+        for_tuples!( #( Tuple::validate_witness(traces)?; )* );
+        Ok(())
+    }
 }
 
 pub fn generate_interaction_trace<C: MachineChip>(
@@ -121,13 +208,13 @@ pub fn generate_interaction_trace<C: MachineChip>(
     if lookup_elements.is_empty() {
         return (ColumnVec::new(), SecureField::zero());
     }
-    let mut logup_trace_gen = LogupTraceGenerator::new(original_traces.log_size());
+    let mut interaction_trace = InteractionTraceBuilder::new(original_traces.log_size());
     C::fill_interaction_trace(
-        &mut logup_trace_gen,
+        &mut interaction_trace,
         original_traces,
         preprocessed_trace,
         program_traces,
         lookup_elements,
     );
-    logup_trace_gen.finalize_last()
+    interaction_trace.build()
 }
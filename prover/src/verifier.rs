@@ -0,0 +1,152 @@
+//! Verification of serialized proofs, intended for verifiers running separately from the prover.
+//!
+//! Proofs are serialized with [`serialize_proof`](crate::serialization::serialize_proof).
+
+use std::fmt;
+
+use itertools::Itertools;
+use stwo_prover::core::pcs::PcsConfig;
+
+use nexus_vm::{
+    emulator::{InternalView, MemoryInitializationEntry, ProgramInfo, PublicOutputEntry, View},
+    WORD_SIZE,
+};
+
+use crate::{
+    machine::{BaseComponent, Machine, PREPROCESSED_COMMITMENT_MISMATCH},
+    serialization::{deserialize_proof, ProofDeserializeError},
+    Proof, VerificationError,
+};
+
+/// Public values of an execution that a proof is checked against.
+#[derive(Debug, Clone)]
+pub struct PublicInputs {
+    pub program_info: ProgramInfo,
+    pub associated_data: Vec<u8>,
+    /// Publicly known initial memory, including the public input.
+    pub init_memory: Vec<MemoryInitializationEntry>,
+    pub exit_code: Vec<PublicOutputEntry>,
+    pub output_memory: Vec<PublicOutputEntry>,
+}
+
+impl PublicInputs {
+    /// Returns the exit code of the execution, or `None` if the program didn't write a full exit code word.
+    ///
+    /// The exit code is a part of the public output, which is bound to the proof by the preprocessed commitment.
+    pub fn exit_code_value(&self) -> Option<u32> {
+        let bytes: [u8; WORD_SIZE] = self
+            .exit_code
+            .iter()
+            .sorted_by_key(|entry| entry.address)
+            .map(|entry| entry.value)
+            .collect::<Vec<u8>>()
+            .try_into()
+            .ok()?;
+        Some(u32::from_le_bytes(bytes))
+    }
+}
+
+impl From<&View> for PublicInputs {
+    fn from(view: &View) -> Self {
+        Self {
+            program_info: view.get_program_memory().clone(),
+            associated_data: view.view_associated_data().unwrap_or_default(),
+            init_memory: view.get_initial_memory().to_vec(),
+            exit_code: view.get_exit_code().to_vec(),
+            output_memory: view.get_public_output().to_vec(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum VerifyError {
+    /// The proof doesn't prove an execution.
+    InvalidProof(VerificationError),
+    /// The proof was generated for different public inputs.
+    PublicInputMismatch,
+    /// The proof is serialized with an unknown format version.
+    UnsupportedVersion(u8),
+    /// The proof bytes cannot be deserialized.
+    MalformedData(ProofDeserializeError),
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidProof(err) => write!(f, "invalid proof: {err}"),
+            Self::PublicInputMismatch => {
+                write!(f, "the proof doesn't match the expected public inputs")
+            }
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported proof format version {version}")
+            }
+            Self::MalformedData(err) => write!(f, "malformed proof data: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for VerifyError {}
+
+impl From<VerificationError> for VerifyError {
+    fn from(err: VerificationError) -> Self {
+        match err {
+            VerificationError::InvalidStructure(msg)
+                if msg.starts_with(PREPROCESSED_COMMITMENT_MISMATCH) =>
+            {
+                Self::PublicInputMismatch
+            }
+            err => Self::InvalidProof(err),
+        }
+    }
+}
+
+impl From<ProofDeserializeError> for VerifyError {
+    fn from(err: ProofDeserializeError) -> Self {
+        match err {
+            ProofDeserializeError::UnsupportedVersion(version) => Self::UnsupportedVersion(version),
+            err => Self::MalformedData(err),
+        }
+    }
+}
+
+/// Configuration of [`verify_execution`], must match the [`ProverConfig`](crate::execution::ProverConfig) of the proof.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VerifierConfig {
+    pub pcs_config: PcsConfig,
+}
+
+/// Verifies a serialized proof of an execution with the given public inputs.
+pub fn verify_execution_proof(
+    proof_bytes: &[u8],
+    public_inputs: &PublicInputs,
+) -> Result<(), VerifyError> {
+    let proof = deserialize_proof(proof_bytes)?;
+    verify_with_public_inputs(proof, public_inputs, VerifierConfig::default())
+}
+
+/// Verifies a proof returned by [`prove_execution`](crate::execution::prove_execution).
+pub fn verify_execution(
+    proof: &Proof,
+    public_inputs: &PublicInputs,
+    config: VerifierConfig,
+) -> Result<(), VerifyError> {
+    verify_with_public_inputs(proof.clone(), public_inputs, config)
+}
+
+fn verify_with_public_inputs(
+    proof: Proof,
+    public_inputs: &PublicInputs,
+    config: VerifierConfig,
+) -> Result<(), VerifyError> {
+    Machine::<BaseComponent>::verify_with_config(
+        &[],
+        proof,
+        &public_inputs.program_info,
+        &public_inputs.associated_data,
+        &public_inputs.init_memory,
+        &public_inputs.exit_code,
+        &public_inputs.output_memory,
+        config.pcs_config,
+    )?;
+    Ok(())
+}
@@ -8,9 +8,10 @@ use stwo_prover::{
 
 use crate::{
     column::Column::{
-        self, ImmC, IsAdd, IsAnd, IsAuipc, IsBeq, IsBge, IsBgeu, IsBlt, IsBltu, IsBne, IsEbreak,
-        IsEcall, IsJal, IsJalr, IsLb, IsLbu, IsLh, IsLhu, IsLui, IsLw, IsOr, IsSb, IsSh, IsSll,
-        IsSlt, IsSltu, IsSra, IsSrl, IsSub, IsSw, IsXor,
+        self, ImmC, IsAdd, IsAnd, IsAuipc, IsBeq, IsBge, IsBgeu, IsBlt, IsBltu, IsBne, IsDiv,
+        IsDivu, IsEbreak, IsEcall, IsFence, IsJal, IsJalr, IsLb, IsLbu, IsLh, IsLhu, IsLui, IsLw,
+        IsMul, IsMulh, IsMulhsu, IsMulhu, IsOr, IsRem, IsRemu, IsSb, IsSh, IsSll, IsSlt, IsSltu,
+        IsSra, IsSrl, IsSub, IsSw, IsXor,
     },
     trace::{eval::trace_eval, eval::TraceEval, FinalizedTraces, TracesBuilder},
 };
@@ -65,8 +66,9 @@ impl<S: VirtualColumnForSum> VirtualColumn<1> for S {
 pub(crate) struct IsTypeR;
 
 impl IsTypeR {
-    const TYPE_R_OPS: [Column; 10] = [
-        IsAdd, IsSub, IsSlt, IsSltu, IsXor, IsOr, IsAnd, IsSll, IsSrl, IsSra,
+    const TYPE_R_OPS: [Column; 18] = [
+        IsAdd, IsSub, IsSlt, IsSltu, IsXor, IsOr, IsAnd, IsSll, IsSrl, IsSra, IsMul, IsMulh,
+        IsMulhsu, IsMulhu, IsDiv, IsDivu, IsRem, IsRemu,
     ];
 }
 
@@ -119,7 +121,8 @@ pub(crate) struct IsAlu;
 impl VirtualColumnForSum for IsAlu {
     fn columns() -> &'static [Column] {
         &[
-            IsAdd, IsSub, IsSlt, IsSltu, IsXor, IsOr, IsAnd, IsSll, IsSrl, IsSra,
+            IsAdd, IsSub, IsSlt, IsSltu, IsXor, IsOr, IsAnd, IsSll, IsSrl, IsSra, IsMul, IsMulh,
+            IsMulhsu, IsMulhu, IsDiv, IsDivu, IsRem, IsRemu,
         ]
     }
 }
@@ -242,16 +245,19 @@ impl VirtualColumn<1> for IsAluImmShift {
     }
 }
 
-/// is_type_i_no_shift = is_load + is_alu_imm_no_shift + is_jalr
+/// is_type_i_no_shift = is_load + is_alu_imm_no_shift + is_jalr + is_fence
+///
+/// FENCE is decoded as a type I instruction, but it accesses no registers, so it is left out of IsTypeI.
 pub(crate) struct IsTypeINoShift;
 
 impl VirtualColumn<1> for IsTypeINoShift {
     fn read_from_traces_builder(traces: &TracesBuilder, row_idx: usize) -> [BaseField; 1] {
         let [is_jalr] = traces.column(row_idx, IsJalr);
+        let [is_fence] = traces.column(row_idx, IsFence);
         let [is_load] = IsLoad::read_from_traces_builder(traces, row_idx);
         let [is_alu_imm_no_shift] = IsAluImmNoShift::read_from_traces_builder(traces, row_idx);
 
-        let ret = is_load + is_alu_imm_no_shift + is_jalr;
+        let ret = is_load + is_alu_imm_no_shift + is_jalr + is_fence;
         [ret]
     }
 
@@ -260,19 +266,21 @@ impl VirtualColumn<1> for IsTypeINoShift {
         vec_idx: usize,
     ) -> [PackedBaseField; 1] {
         let is_jalr = traces.get_base_column::<1>(IsJalr)[0].data[vec_idx];
+        let is_fence = traces.get_base_column::<1>(IsFence)[0].data[vec_idx];
         let [is_load] = IsLoad::read_from_finalized_traces(traces, vec_idx);
         let [is_alu_imm_no_shift] = IsAluImmNoShift::read_from_finalized_traces(traces, vec_idx);
 
-        let ret = is_load + is_alu_imm_no_shift + is_jalr;
+        let ret = is_load + is_alu_imm_no_shift + is_jalr + is_fence;
         [ret]
     }
 
     fn eval<E: EvalAtRow>(trace_eval: &TraceEval<E>) -> [E::F; 1] {
         let [is_jalr] = trace_eval!(trace_eval, IsJalr);
+        let [is_fence] = trace_eval!(trace_eval, IsFence);
         let [is_load] = IsLoad::eval(trace_eval);
         let [is_alu_imm_no_shift] = IsAluImmNoShift::eval(trace_eval);
 
-        let ret = is_load + is_alu_imm_no_shift + is_jalr;
+        let ret = is_load + is_alu_imm_no_shift + is_jalr + is_fence;
         [ret]
     }
 }
@@ -294,7 +302,7 @@ impl VirtualColumnForSum for IsTypeB {
 }
 
 /// Instead of having is_pc_incremented as a separate column and having
-/// `(is_alu + is_load + is_type_s + is_type_sys + is_type_u - is_pc_incremented) = 0`,
+/// `(is_alu + is_load + is_type_s + is_type_sys + is_type_u + is_fence - is_pc_incremented) = 0`,
 /// we can just have a virtual column is_pc_incremented. This change doesn't change the degree of any constraints.
 pub(crate) struct IsPcIncremented;
 
@@ -307,11 +315,13 @@ impl VirtualColumn<1> for IsPcIncremented {
         let [is_type_sys] = IsTypeSys::read_from_traces_builder(traces, row_idx);
 
         let [is_sys_halt] = traces.column(row_idx, Column::IsSysHalt);
+        let [is_fence] = traces.column(row_idx, IsFence);
         let ret = is_alu
             + is_load
             + is_type_s
             + is_type_sys * (BaseField::one() - is_sys_halt)
-            + is_type_u;
+            + is_type_u
+            + is_fence;
         [ret]
     }
     fn read_from_finalized_traces(
@@ -325,11 +335,13 @@ impl VirtualColumn<1> for IsPcIncremented {
         let is_type_sys = IsTypeSys::read_from_finalized_traces(traces, vec_idx)[0];
 
         let is_sys_halt = traces.get_base_column::<1>(Column::IsSysHalt)[0].data[vec_idx];
+        let is_fence = traces.get_base_column::<1>(IsFence)[0].data[vec_idx];
         let ret = is_alu
             + is_load
             + is_type_s
             + is_type_sys * (PackedBaseField::one() - is_sys_halt)
-            + is_type_u;
+            + is_type_u
+            + is_fence;
         [ret]
     }
     fn eval<E: EvalAtRow>(trace_eval: &TraceEval<E>) -> [E::F; 1] {
@@ -340,8 +352,13 @@ impl VirtualColumn<1> for IsPcIncremented {
         let [is_type_sys] = IsTypeSys::eval(trace_eval);
 
         let [is_sys_halt] = trace_eval!(trace_eval, Column::IsSysHalt);
-        let ret =
-            is_alu + is_load + is_type_s + is_type_sys * (E::F::one() - is_sys_halt) + is_type_u;
+        let [is_fence] = trace_eval!(trace_eval, IsFence);
+        let ret = is_alu
+            + is_load
+            + is_type_s
+            + is_type_sys * (E::F::one() - is_sys_halt)
+            + is_type_u
+            + is_fence;
         [ret]
     }
 }
@@ -360,8 +377,8 @@ impl VirtualColumnForSum for OpBFlag {
     fn columns() -> &'static [Column] {
         &[
             IsSb, IsSh, IsSw, IsLb, IsLh, IsLw, IsLbu, IsLhu, IsJalr, IsAdd, IsSub, IsSlt, IsSltu,
-            IsXor, IsOr, IsAnd, IsSll, IsSrl, IsSra, IsBeq, IsBne, IsBlt, IsBge, IsBltu, IsBgeu,
-            IsEcall, IsEbreak,
+            IsXor, IsOr, IsAnd, IsSll, IsSrl, IsSra, IsMul, IsMulh, IsMulhsu, IsMulhu, IsDiv,
+            IsDivu, IsRem, IsRemu, IsBeq, IsBne, IsBlt, IsBge, IsBltu, IsBgeu, IsEcall, IsEbreak,
         ]
     }
 }
@@ -7,8 +7,12 @@ mod test {
     use nexus_common_testing::program_trace;
     use nexus_vm::elf::ElfFile;
     use nexus_vm::emulator::InternalView;
+    use nexus_vm::emulator::PublicOutputEntry;
     use nexus_vm::trace::{k_trace, k_trace_direct};
-    use nexus_vm_prover::{prove, verify};
+    use nexus_vm_prover::{
+        deserialize_proof, prove, prove_execution, serialize_proof, verify, verify_execution,
+        verify_execution_proof, ProverConfig, PublicInputs, VerifierConfig, VerifyError,
+    };
     use postcard::to_allocvec_cobs;
     use serial_test::serial;
     const K: usize = 1;
@@ -183,6 +187,36 @@ mod test {
         verify(proof, &view).unwrap();
     }
 
+    #[test]
+    #[serial]
+    fn test_prove_execution_fact() {
+        let elfs = compile_multi("examples/src/bin/fact", &["-C opt-level=3"], &HOME_PATH);
+        let (proof, public_inputs) =
+            prove_execution(&elfs[0], &[], &[], ProverConfig::default()).unwrap();
+        verify_execution(&proof, &public_inputs, VerifierConfig::default()).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_prove_execution_exit_code() {
+        let elfs = compile_multi(
+            "examples/src/bin/exit_code",
+            &["-C opt-level=3"],
+            &HOME_PATH,
+        );
+        let (proof, public_inputs) =
+            prove_execution(&elfs[0], &[], &[], ProverConfig::default()).unwrap();
+        assert_eq!(public_inputs.exit_code_value(), Some(42));
+        verify_execution(&proof, &public_inputs, VerifierConfig::default()).unwrap();
+
+        let mut wrong_exit_code = public_inputs.clone();
+        wrong_exit_code.exit_code[0].value ^= 1;
+        assert!(matches!(
+            verify_execution(&proof, &wrong_exit_code, VerifierConfig::default()),
+            Err(VerifyError::PublicInputMismatch)
+        ));
+    }
+
     #[test]
     #[serial]
     fn test_emulate_fib() {
@@ -623,6 +657,34 @@ mod test {
         verify(proof, &view).unwrap();
     }
 
+    #[test]
+    #[serial]
+    fn test_verify_serialized_proof() {
+        let log_size = 16;
+        let blocks = program_trace(log_size);
+        let (view, execution_trace) = k_trace_direct(&blocks, K).expect("error generating trace");
+        let proof_bytes = serialize_proof(&prove(&execution_trace, &view).unwrap());
+        verify(deserialize_proof(&proof_bytes).unwrap(), &view).unwrap();
+
+        let public_inputs = PublicInputs::from(&view);
+        verify_execution_proof(&proof_bytes, &public_inputs).unwrap();
+
+        let mut wrong_output = public_inputs.clone();
+        wrong_output.output_memory.push(PublicOutputEntry {
+            address: 0x1000,
+            value: 1,
+        });
+        assert!(matches!(
+            verify_execution_proof(&proof_bytes, &wrong_output),
+            Err(VerifyError::PublicInputMismatch)
+        ));
+
+        assert!(matches!(
+            verify_execution_proof(&proof_bytes[..proof_bytes.len() / 2], &public_inputs),
+            Err(VerifyError::MalformedData(_))
+        ));
+    }
+
     #[test]
     #[ignore]
     fn test_serialize_proofs() {
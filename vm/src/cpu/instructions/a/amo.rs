@@ -0,0 +1,126 @@
+use crate::{
+    cpu::state::{InstructionExecutor, InstructionState},
+    memory::{LoadOp, LoadOps, MemAccessSize, MemoryProcessor, StoreOps},
+    riscv::{Instruction, Register},
+};
+use nexus_common::{
+    cpu::{Processor, Registers},
+    error::MemoryError,
+};
+
+/// Non-atomic emulation of LR.W, SC.W, and the word-sized AMO instructions.
+///
+/// Without concurrent harts a reservation is never broken, so SC.W always succeeds.
+pub struct AmoInstruction {
+    funct5: u8,
+    rd: (Register, u32),
+    address: u32,
+    rs2: u32,
+    // Value written to memory
+    result: u32,
+}
+
+const LR_W: u8 = 0b00010;
+const SC_W: u8 = 0b00011;
+
+impl InstructionState for AmoInstruction {
+    fn memory_read(&mut self, memory: &impl MemoryProcessor) -> Result<LoadOps, MemoryError> {
+        if self.funct5 == SC_W {
+            return <AmoInstruction as InstructionState>::readless();
+        }
+        let op = memory.read(self.address, MemAccessSize::Word)?;
+        let LoadOp::Op(_, _, value) = op;
+        self.rd.1 = value;
+        Ok(op.into())
+    }
+
+    fn execute(&mut self) {
+        let (loaded, rs2) = (self.rd.1, self.rs2);
+        self.result = match self.funct5 {
+            0b00001 | SC_W => rs2,
+            0b00000 => loaded.wrapping_add(rs2),
+            0b00100 => loaded ^ rs2,
+            0b01100 => loaded & rs2,
+            0b01000 => loaded | rs2,
+            0b10000 => (loaded as i32).min(rs2 as i32) as u32,
+            0b10100 => (loaded as i32).max(rs2 as i32) as u32,
+            0b11000 => loaded.min(rs2),
+            0b11100 => loaded.max(rs2),
+            _ => loaded,
+        };
+        if self.funct5 == SC_W {
+            // The reservation always holds.
+            self.rd.1 = 0;
+        }
+    }
+
+    fn memory_write(&self, memory: &mut impl MemoryProcessor) -> Result<StoreOps, MemoryError> {
+        if self.funct5 == LR_W {
+            return <AmoInstruction as InstructionState>::writeless();
+        }
+        Ok(memory
+            .write(self.address, MemAccessSize::Word, self.result)?
+            .into())
+    }
+
+    fn write_back(&self, cpu: &mut impl Processor) -> Option<u32> {
+        cpu.registers_mut().write(self.rd.0, self.rd.1);
+        Some(self.rd.1)
+    }
+}
+
+impl InstructionExecutor for AmoInstruction {
+    type InstructionState = Self;
+
+    fn decode(ins: &Instruction, registers: &impl Registers) -> Self {
+        Self {
+            funct5: ins.opcode.fn7().value() >> 2,
+            rd: (ins.op_a, 0),
+            address: registers[ins.op_b],
+            rs2: registers[Register::from(ins.op_c as u8)],
+            result: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::state::Cpu;
+    use crate::memory::{VariableMemory, RW};
+    use crate::riscv::decode_instruction;
+
+    fn evaluate(cpu: &mut Cpu, memory: &mut VariableMemory<RW>, encoded: u32) -> Option<u32> {
+        let ins = decode_instruction(encoded);
+        let (res, _) = AmoInstruction::evaluator(cpu, memory, &ins).unwrap();
+        res
+    }
+
+    #[test]
+    fn test_amo_instructions() {
+        let mut cpu = Cpu::default();
+        let mut memory = VariableMemory::<RW>::default();
+        cpu.registers.write(Register::X11, 0x1000);
+        cpu.registers.write(Register::X12, 5);
+        memory.write(0x1000, MemAccessSize::Word, 10).unwrap();
+
+        // amoadd.w a0, a2, (a1)
+        assert_eq!(evaluate(&mut cpu, &mut memory, 0x00c5a52f), Some(10));
+        assert_eq!(cpu.registers.read(Register::X10), 10);
+        // amomaxu.w a0, a2, (a1)
+        assert_eq!(evaluate(&mut cpu, &mut memory, 0xe0c5a52f), Some(15));
+        // amomin.w a0, a2, (a1)
+        assert_eq!(evaluate(&mut cpu, &mut memory, 0x80c5a52f), Some(15));
+        // amoswap.w.aqrl a0, a2, (a1)
+        assert_eq!(evaluate(&mut cpu, &mut memory, 0x0ec5a52f), Some(5));
+
+        // lr.w.aq a0, (a1) followed by sc.w a3, a2, (a1)
+        cpu.registers.write(Register::X12, 7);
+        assert_eq!(evaluate(&mut cpu, &mut memory, 0x1405a52f), Some(5));
+        assert_eq!(evaluate(&mut cpu, &mut memory, 0x18c5a6af), Some(0));
+        assert_eq!(
+            memory.read(0x1000, MemAccessSize::Word).unwrap(),
+            LoadOp::Op(MemAccessSize::Word, 0x1000, 7)
+        );
+    }
+}
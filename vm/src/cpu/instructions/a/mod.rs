@@ -0,0 +1,99 @@
+// RV32A Atomic extension
+//
+// The zkVM executes a single hart, so atomic instructions are not supported by default: they fail with
+// `VMError::AtomicOperationInSingleThreadedContext`. With the `emulate-atomics` feature they are executed
+// non-atomically instead, which is sufficient for programs that use atomics without relying on concurrency.
+
+use crate::{
+    cpu::state::Cpu,
+    error::{Result, VMError},
+    riscv::Instruction,
+};
+
+#[cfg(feature = "emulate-atomics")]
+mod amo;
+#[cfg(feature = "emulate-atomics")]
+pub use amo::AmoInstruction;
+
+/// Major opcode of the LR.W, SC.W, and AMO instructions.
+pub const AMO_OPCODE: u8 = 0b0101111;
+
+/// Returns the name of the word-sized atomic instruction with the given `funct5` field.
+pub(crate) fn amo_name(funct5: u8) -> Option<&'static str> {
+    Some(match funct5 {
+        0b00010 => "lr.w",
+        0b00011 => "sc.w",
+        0b00001 => "amoswap.w",
+        0b00000 => "amoadd.w",
+        0b00100 => "amoxor.w",
+        0b01100 => "amoand.w",
+        0b01000 => "amoor.w",
+        0b10000 => "amomin.w",
+        0b10100 => "amomax.w",
+        0b11000 => "amominu.w",
+        0b11100 => "amomaxu.w",
+        _ => return None,
+    })
+}
+
+/// Returns `true` if `ins` is an atomic memory instruction.
+pub fn is_atomic_instruction(ins: &Instruction) -> bool {
+    !ins.opcode.is_builtin() && ins.opcode.raw() == AMO_OPCODE
+}
+
+/// Returns [`VMError::AtomicOperationInSingleThreadedContext`] if `ins` is an atomic memory instruction.
+///
+/// Used unless atomics are emulated with the `emulate-atomics` feature.
+pub fn check_atomic_operation(cpu: &Cpu, ins: &Instruction) -> Result<()> {
+    if !is_atomic_instruction(ins) {
+        return Ok(());
+    }
+    Err(VMError::AtomicOperationInSingleThreadedContext {
+        opcode: encode_atomic_instruction(ins),
+        pc: cpu.pc.value,
+    })
+}
+
+// The common encoder only supports builtin opcodes, atomic instructions are R-type.
+fn encode_atomic_instruction(ins: &Instruction) -> u32 {
+    ((ins.opcode.fn7().value() as u32) << 25)
+        | ((ins.op_c & 0x1F) << 20)
+        | ((ins.op_b as u32) << 15)
+        | ((ins.opcode.fn3().value() as u32) << 12)
+        | ((ins.op_a as u32) << 7)
+        | ins.opcode.raw() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::riscv::{decode_instruction, BuiltinOpcode, Opcode};
+
+    #[test]
+    fn test_check_atomic_operation() {
+        let mut cpu = Cpu::default();
+        cpu.pc.value = 0x1000;
+
+        // amoadd.w a0, a2, (a1)
+        let amoadd = decode_instruction(0x00c5a52f);
+        assert_eq!(amoadd.opcode.name(), "amoadd.w");
+        let err = check_atomic_operation(&cpu, &amoadd).unwrap_err();
+        assert_eq!(
+            err,
+            VMError::AtomicOperationInSingleThreadedContext {
+                opcode: 0x00c5a52f,
+                pc: 0x1000
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "Atomic instruction 0x00C5A52F at pc=0x00001000 is not supported, the zkVM executes a single thread"
+        );
+
+        // lr.w.aq a0, (a1)
+        assert_eq!(decode_instruction(0x1405a52f).opcode.name(), "lr.w");
+
+        let add = Instruction::new_ir(Opcode::from(BuiltinOpcode::ADD), 3, 1, 2);
+        assert_eq!(check_atomic_operation(&cpu, &add), Ok(()));
+    }
+}
@@ -0,0 +1,62 @@
+use crate::{
+    cpu::state::{InstructionExecutor, InstructionState},
+    memory::{LoadOps, MemoryProcessor, StoreOps},
+    riscv::Instruction,
+};
+use nexus_common::{
+    cpu::{Processor, Registers},
+    error::MemoryError,
+};
+
+/// FENCE orders memory accesses between harts and devices. The VM executes a single hart without
+/// caches, so every access is already ordered and FENCE is a no-op.
+pub struct FenceInstruction;
+
+impl InstructionState for FenceInstruction {
+    fn memory_read(&mut self, _: &impl MemoryProcessor) -> Result<LoadOps, MemoryError> {
+        <FenceInstruction as InstructionState>::readless()
+    }
+
+    fn memory_write(&self, _: &mut impl MemoryProcessor) -> Result<StoreOps, MemoryError> {
+        <FenceInstruction as InstructionState>::writeless()
+    }
+
+    fn execute(&mut self) {}
+
+    fn write_back(&self, _: &mut impl Processor) -> Option<u32> {
+        None
+    }
+}
+
+impl InstructionExecutor for FenceInstruction {
+    type InstructionState = Self;
+
+    fn decode(_: &Instruction, _: &impl Registers) -> Self {
+        Self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::state::Cpu;
+    use crate::riscv::{BuiltinOpcode, Opcode, Register};
+
+    #[test]
+    fn test_fence_is_noop() {
+        let mut cpu = Cpu::default();
+        for reg in 1..32u32 {
+            cpu.registers
+                .write(Register::from(reg as u8), reg * 0x0101_0101);
+        }
+        let registers = cpu.registers;
+
+        let bare_instruction = Instruction::new_ir(Opcode::from(BuiltinOpcode::FENCE), 0, 0, 0xff);
+        let mut instruction = FenceInstruction::decode(&bare_instruction, &cpu.registers);
+        instruction.execute();
+        let res = instruction.write_back(&mut cpu);
+
+        assert_eq!(res, None);
+        assert_eq!(cpu.registers, registers);
+    }
+}
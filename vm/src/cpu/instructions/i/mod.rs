@@ -40,6 +40,9 @@ mod jal;
 mod auipc;
 mod lui;
 
+// Memory ordering
+mod fence;
+
 pub use add::AddInstruction;
 pub use and::AndInstruction;
 pub use or::OrInstruction;
@@ -67,3 +70,92 @@ pub use jal::{JalInstruction, JalrInstruction};
 
 pub use auipc::AuipcInstruction;
 pub use lui::LuiInstruction;
+
+pub use fence::FenceInstruction;
+
+use crate::{
+    cpu::state::Cpu,
+    error::{Result, VMError},
+    riscv::{BuiltinOpcode, Instruction},
+};
+
+/// Returns [`VMError::InvalidAlignment`] if `ins` is a halfword or word load or store whose address isn't a
+/// multiple of the access size.
+///
+/// Byte accesses are never misaligned.
+pub fn check_memory_alignment(cpu: &Cpu, ins: &Instruction) -> Result<()> {
+    let (base, required_alignment) = match ins.opcode.builtin() {
+        Some(BuiltinOpcode::LH | BuiltinOpcode::LHU) => (ins.op_b, 2),
+        Some(BuiltinOpcode::LW) => (ins.op_b, 4),
+        Some(BuiltinOpcode::SH) => (ins.op_a, 2),
+        Some(BuiltinOpcode::SW) => (ins.op_a, 4),
+        _ => return Ok(()),
+    };
+    let addr = cpu.registers[base].wrapping_add(ins.op_c);
+    if addr % required_alignment == 0 {
+        return Ok(());
+    }
+    Err(VMError::InvalidAlignment {
+        addr,
+        required_alignment,
+        pc: cpu.pc.value,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::riscv::{Opcode, Register};
+    use nexus_common::cpu::Registers;
+
+    #[test]
+    fn test_check_memory_alignment() {
+        let mut cpu = Cpu::default();
+        cpu.pc.value = 0x1000;
+        cpu.registers.write(Register::X1, 0x2001);
+
+        let lw = Instruction::new_ir(Opcode::from(BuiltinOpcode::LW), 2, 1, 1);
+        assert_eq!(
+            check_memory_alignment(&cpu, &lw),
+            Err(VMError::InvalidAlignment {
+                addr: 0x2002,
+                required_alignment: 4,
+                pc: 0x1000
+            })
+        );
+        assert_eq!(
+            check_memory_alignment(&cpu, &lw).unwrap_err().to_string(),
+            "Misaligned memory access to 0x00002002, requires 4-byte alignment, pc=0x00001000"
+        );
+        let lh = Instruction::new_ir(Opcode::from(BuiltinOpcode::LH), 2, 1, 1);
+        assert_eq!(check_memory_alignment(&cpu, &lh), Ok(()));
+        let lhu = Instruction::new_ir(Opcode::from(BuiltinOpcode::LHU), 2, 1, 0);
+        assert_eq!(
+            check_memory_alignment(&cpu, &lhu),
+            Err(VMError::InvalidAlignment {
+                addr: 0x2001,
+                required_alignment: 2,
+                pc: 0x1000
+            })
+        );
+
+        // Stores address memory with op_a, a negative offset can realign the address.
+        let sw = Instruction::new_ir(Opcode::from(BuiltinOpcode::SW), 1, 2, -1i32 as u32);
+        assert_eq!(check_memory_alignment(&cpu, &sw), Ok(()));
+        let sh = Instruction::new_ir(Opcode::from(BuiltinOpcode::SH), 1, 2, 2);
+        assert_eq!(
+            check_memory_alignment(&cpu, &sh),
+            Err(VMError::InvalidAlignment {
+                addr: 0x2003,
+                required_alignment: 2,
+                pc: 0x1000
+            })
+        );
+
+        // Byte accesses are never misaligned.
+        for opcode in [BuiltinOpcode::LB, BuiltinOpcode::LBU, BuiltinOpcode::SB] {
+            let ins = Instruction::new_ir(Opcode::from(opcode), 1, 1, 0);
+            assert_eq!(check_memory_alignment(&cpu, &ins), Ok(()));
+        }
+    }
+}
@@ -11,3 +11,72 @@ pub use div::{DivInstruction, DivuInstruction};
 pub use mul::MulInstruction;
 pub use mulh::{MulhInstruction, MulhsuInstruction, MulhuInstruction};
 pub use rem::{RemInstruction, RemuInstruction};
+
+use crate::{
+    cpu::state::Cpu,
+    error::{Result, VMError},
+    riscv::{BuiltinOpcode, Instruction, Register},
+};
+
+/// Returns [`VMError::DivisionByZero`] if `ins` is a DIV, DIVU, REM or REMU instruction with a zero divisor.
+///
+/// Division by zero is well-defined in RISC-V, so the executors never fail on it; this check is only used
+/// when the emulator is configured to trap on division by zero.
+pub fn check_division_by_zero(cpu: &Cpu, ins: &Instruction) -> Result<()> {
+    if !matches!(
+        ins.opcode.builtin(),
+        Some(BuiltinOpcode::DIV)
+            | Some(BuiltinOpcode::DIVU)
+            | Some(BuiltinOpcode::REM)
+            | Some(BuiltinOpcode::REMU)
+    ) {
+        return Ok(());
+    }
+    if cpu.registers[Register::from(ins.op_c as u8)] != 0 {
+        return Ok(());
+    }
+    Err(VMError::DivisionByZero {
+        pc: cpu.pc.value,
+        opcode: ins.encode(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::riscv::Opcode;
+    use nexus_common::cpu::Registers;
+
+    #[test]
+    fn test_check_division_by_zero() {
+        let mut cpu = Cpu::default();
+        cpu.pc.value = 0x1000;
+        cpu.registers.write(Register::X1, 20);
+
+        let divu = Instruction::new_ir(Opcode::from(BuiltinOpcode::DIVU), 3, 1, 2);
+        let err = check_division_by_zero(&cpu, &divu).unwrap_err();
+        assert_eq!(
+            err,
+            VMError::DivisionByZero {
+                pc: 0x1000,
+                opcode: divu.encode()
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "Division by zero (unsigned) at pc=0x00001000"
+        );
+
+        let rem = Instruction::new_ir(Opcode::from(BuiltinOpcode::REM), 3, 1, 2);
+        assert_eq!(
+            check_division_by_zero(&cpu, &rem).unwrap_err().to_string(),
+            "Division by zero (signed) at pc=0x00001000"
+        );
+
+        // non-zero divisor and non-division instructions pass
+        cpu.registers.write(Register::X2, 3);
+        assert_eq!(check_division_by_zero(&cpu, &divu), Ok(()));
+        let mul = Instruction::new_ir(Opcode::from(BuiltinOpcode::MUL), 3, 1, 0);
+        assert_eq!(check_division_by_zero(&cpu, &mul), Ok(()));
+    }
+}
@@ -1,21 +1,29 @@
 // RV32I Base Integer Instructions
 mod i;
 pub use i::{
-    AddInstruction, AndInstruction, AuipcInstruction, BeqInstruction, BgeInstruction,
-    BgeuInstruction, BltInstruction, BltuInstruction, BneInstruction, JalInstruction,
-    JalrInstruction, LbInstruction, LbuInstruction, LhInstruction, LhuInstruction, LuiInstruction,
-    LwInstruction, OrInstruction, SbInstruction, ShInstruction, SllInstruction, SltInstruction,
-    SltuInstruction, SraInstruction, SrlInstruction, SubInstruction, SwInstruction, XorInstruction,
+    check_memory_alignment, AddInstruction, AndInstruction, AuipcInstruction, BeqInstruction,
+    BgeInstruction, BgeuInstruction, BltInstruction, BltuInstruction, BneInstruction,
+    FenceInstruction, JalInstruction, JalrInstruction, LbInstruction, LbuInstruction,
+    LhInstruction, LhuInstruction, LuiInstruction, LwInstruction, OrInstruction, SbInstruction,
+    ShInstruction, SllInstruction, SltInstruction, SltuInstruction, SraInstruction, SrlInstruction,
+    SubInstruction, SwInstruction, XorInstruction,
 };
 
 // RV32M Multiply extension
 mod m;
 pub use m::{
-    DivInstruction, DivuInstruction, MulInstruction, MulhInstruction, MulhsuInstruction,
-    MulhuInstruction, RemInstruction, RemuInstruction,
+    check_division_by_zero, DivInstruction, DivuInstruction, MulInstruction, MulhInstruction,
+    MulhsuInstruction, MulhuInstruction, RemInstruction, RemuInstruction,
 };
 
 pub use nexus_common::cpu::InstructionResult;
 
+// RV32A Atomic extension
+mod a;
+pub(crate) use a::amo_name;
+#[cfg(feature = "emulate-atomics")]
+pub use a::AmoInstruction;
+pub use a::{check_atomic_operation, is_atomic_instruction, AMO_OPCODE};
+
 // Macro implementations
 pub(crate) mod macros;
@@ -22,6 +22,8 @@
 //!
 //! - `ElfFile::from_bytes`: Allows creation of `ElfFile` from raw bytes
 //! - `ElfFile::from_path`: Allows creation of `ElfFile` from a file path
+//! - `load_elf`: Creates an `ElfFile` and its `SymbolTable` from raw bytes, additionally validating
+//!   that the loadable segments don't overlap
 //!
 //! # Usage
 //!
@@ -44,13 +46,15 @@
 //! a Harvard architecture model. Ensure your ELF files are compatible with these specifications.
 
 use crate::elf::parser;
+use crate::error::VMError;
 
-use elf::{endian::LittleEndian, ElfBytes};
+use elf::{abi, endian::LittleEndian, ElfBytes};
 use std::collections::BTreeMap;
 use std::fs::File;
 use std::path::Path;
 
 use super::error::ParserError;
+use super::symbols::SymbolTable;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -129,6 +133,40 @@ impl ElfFile {
     }
 }
 
+/// Parses a RISC-V 32-bit executable, and extracts its symbol table for profiling and error messages.
+///
+/// Returns [`VMError::InvalidMemoryLayout`] if any `PT_LOAD` segment exceeds the 32-bit address space
+/// or overlaps with another one, and [`VMError::InvalidElf`] for any other parsing error, including
+/// non-RISC-V targets.
+pub fn load_elf(bytes: &[u8]) -> Result<(ElfFile, SymbolTable), VMError> {
+    let invalid_elf = |err: ParserError| VMError::InvalidElf(err.to_string());
+
+    let elf = ElfBytes::<LittleEndian>::minimal_parse(bytes)
+        .map_err(|err| invalid_elf(ParserError::ELFError(err)))?;
+    parser::validate_elf_header(&elf.ehdr).map_err(invalid_elf)?;
+
+    let segments = elf
+        .segments()
+        .ok_or(ParserError::NoSegmentAvailable)
+        .map_err(invalid_elf)?;
+    let mut ranges = Vec::new();
+    for segment in segments.iter().filter(|x| x.p_type == abi::PT_LOAD) {
+        let end = segment.p_vaddr.checked_add(segment.p_memsz);
+        match end {
+            Some(end) if end <= u32::MAX as u64 + 1 => ranges.push(segment.p_vaddr..end),
+            _ => return Err(VMError::InvalidMemoryLayout),
+        }
+    }
+    ranges.sort_by_key(|range| range.start);
+    if ranges.windows(2).any(|pair| pair[0].end > pair[1].start) {
+        return Err(VMError::InvalidMemoryLayout);
+    }
+
+    let symbols = SymbolTable::from_elf(&elf).map_err(invalid_elf)?;
+    let elf = ElfFile::from_bytes(bytes).map_err(invalid_elf)?;
+    Ok((elf, symbols))
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -169,6 +207,27 @@ mod tests {
         write_memory_to_file(&elf.rom_image, &format!("{}.rom.bin", file_path));
     }
 
+    #[test]
+    fn test_load_elf() {
+        let bytes = std::fs::read("test/fib_10.elf").unwrap();
+        let (elf, symbols) = load_elf(&bytes).unwrap();
+        assert_eq!(elf.entry, 4096);
+        assert!(!symbols.is_empty());
+        assert_eq!(symbols.lookup_pc(elf.entry), Some("_start"));
+        assert_eq!(symbols.lookup_pc(0), None);
+
+        // Not an ELF file.
+        assert!(matches!(load_elf(&bytes[1..]), Err(VMError::InvalidElf(_))));
+
+        // Change e_machine from RISC-V to x86.
+        let mut x86 = bytes.clone();
+        x86[18..20].copy_from_slice(&abi::EM_386.to_le_bytes());
+        assert_eq!(
+            load_elf(&x86).map(|_| ()),
+            Err(VMError::InvalidElf(ParserError::NotRiscV.to_string()))
+        );
+    }
+
     #[test]
     fn test_parse_elf_files() {
         let test_cases = [("test/fib_10.elf", 4096, 4096, 1467)];
@@ -1,7 +1,9 @@
 mod error;
 mod loader;
 mod parser;
+mod symbols;
 
 pub use error::ParserError as ElfError;
-pub use loader::ElfFile;
+pub use loader::{load_elf, ElfFile};
 pub use nexus_common::constants::WORD_SIZE;
+pub use symbols::SymbolTable;
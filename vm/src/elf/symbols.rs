@@ -0,0 +1,103 @@
+//! Symbol table of an ELF file, maps program counters back to function names.
+//!
+//! Only function symbols (`STT_FUNC`) and global labels of executable sections are kept. Symbols
+//! without a size, e.g. those defined in assembly, are assumed to extend up to the next symbol.
+use elf::{abi, endian::LittleEndian, ElfBytes};
+
+use super::error::{ParserError, Result};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Symbol {
+    start: u32,
+    end: u32,
+    section_end: u32,
+    name: String,
+}
+
+/// Function symbols of a program, sorted by address.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SymbolTable {
+    symbols: Vec<Symbol>,
+}
+
+impl SymbolTable {
+    /// Extracts the function symbols of `elf`, returns an empty table if the file is stripped.
+    pub fn from_elf(elf: &ElfBytes<LittleEndian>) -> Result<Self> {
+        let Some((symbol_table, string_table)) = elf.symbol_table()? else {
+            return Ok(Self::default());
+        };
+
+        let section_headers = elf.section_headers().ok_or(ParserError::NoSectionHeader)?;
+
+        let mut symbols = Vec::new();
+        for symbol in symbol_table {
+            let labelled = symbol.st_symtype() == abi::STT_FUNC
+                || (symbol.st_symtype() == abi::STT_NOTYPE && symbol.st_bind() != abi::STB_LOCAL);
+            if !labelled || symbol.is_undefined() || symbol.st_shndx == abi::SHN_ABS {
+                continue;
+            }
+            if symbol.st_shndx == abi::SHN_XINDEX {
+                return Err(ParserError::InvalidSectionIndex(symbol.st_shndx));
+            }
+            let section = section_headers.get(symbol.st_shndx as usize)?;
+            if section.sh_flags & abi::SHF_EXECINSTR as u64 == 0 {
+                continue;
+            }
+
+            let start: u32 = symbol
+                .st_value
+                .try_into()
+                .map_err(|_| ParserError::InvalidVirtualAddress(symbol.st_value))?;
+            let size: u32 = symbol
+                .st_size
+                .try_into()
+                .map_err(|_| ParserError::InvalidMemorySize)?;
+            let end = start
+                .checked_add(size)
+                .ok_or(ParserError::AddressExceedsMemorySize)?;
+            let section_end: u32 = (section.sh_addr + section.sh_size)
+                .try_into()
+                .map_err(|_| ParserError::InvalidSectionAddress(section.sh_addr))?;
+            let name = string_table.get(symbol.st_name as usize)?;
+            symbols.push(Symbol {
+                start,
+                end,
+                section_end,
+                name: name.into(),
+            });
+        }
+
+        // Keep a single symbol per address, preferring the ones with a known size.
+        symbols.sort_by_key(|symbol| (symbol.start, symbol.start == symbol.end));
+        symbols.dedup_by_key(|symbol| symbol.start);
+
+        let next_starts: Vec<u32> = symbols
+            .iter()
+            .skip(1)
+            .map(|symbol| symbol.start)
+            .chain([u32::MAX])
+            .collect();
+        for (symbol, next_start) in symbols.iter_mut().zip(next_starts) {
+            if symbol.start == symbol.end {
+                symbol.end = next_start.min(symbol.section_end);
+            }
+        }
+
+        Ok(Self { symbols })
+    }
+
+    /// Returns the name of the function containing `pc`.
+    pub fn lookup_pc(&self, pc: u32) -> Option<&str> {
+        let idx = self.symbols.partition_point(|symbol| symbol.start <= pc);
+        let symbol = self.symbols.get(idx.checked_sub(1)?)?;
+        (pc < symbol.end).then_some(symbol.name.as_str())
+    }
+
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+}
@@ -134,18 +134,27 @@
 use super::{
     layout::LinearMemoryLayout, memory_stats::*, registry::InstructionExecutorRegistry, *,
 };
+#[cfg(not(feature = "emulate-atomics"))]
+use crate::cpu::instructions::check_atomic_operation;
 use crate::{
-    cpu::{instructions::InstructionResult, Cpu},
+    cpu::{
+        instructions::{check_division_by_zero, check_memory_alignment, InstructionResult},
+        Cpu,
+    },
     elf::ElfFile,
     error::{Result, VMError},
     memory::{
-        FixedMemory, LoadOp, MemoryProcessor, MemoryRecords, Modes, StoreOp, UnifiedMemory,
-        VariableMemory, NA, RO, RW, WO,
+        FixedMemory, LoadOp, MemoryAccessLog, MemoryProcessor, MemoryRecords, Modes, StoreOp,
+        UnifiedMemory, VariableMemory, NA, RO, RW, WO,
     },
-    riscv::{decode_until_end_of_a_block, BasicBlock, Instruction, Opcode, Register},
-    system::SyscallInstruction,
+    riscv::{BasicBlock, BuiltinOpcode, Instruction, Opcode, Register},
+    system::{SyscallInstruction, SyscallState, SyscallTable},
 };
 
+#[cfg(feature = "riscv-c")]
+use crate::riscv::decode_parcels_until_end_of_a_block;
+#[cfg(not(feature = "riscv-c"))]
+use crate::riscv::decode_until_end_of_a_block;
 use nexus_common::{
     constants::{ELF_TEXT_START, MEMORY_TOP, WORD_SIZE},
     cpu::{InstructionExecutor, Registers},
@@ -156,8 +165,12 @@ use rangemap::RangeMap;
 use std::{
     cmp::max,
     collections::{BTreeMap, HashMap, HashSet, VecDeque},
+    time::{Duration, Instant},
 };
 
+/// Number of executed instructions between two checks of the execution timeout.
+const TIMEOUT_CHECK_INTERVAL: usize = 1024;
+
 #[derive(Debug, Default)]
 pub struct Executor {
     // The CPU
@@ -193,6 +206,28 @@ pub struct Executor {
 
     // A map of memory addresses to the last timestamp when they were accessed
     pub access_timestamps: HashMap<u32, usize>,
+
+    // Whether division by zero returns an error instead of the RISC-V defined result
+    trap_division_by_zero: bool,
+
+    // Syscalls recognized by the emulator, custom syscalls can be registered here
+    pub syscalls: SyscallTable,
+
+    // Whether memory accesses are appended to `memory_log`
+    log_memory_accesses: bool,
+
+    // Ordered log of the memory accesses, see `Executor::log_memory_accesses`
+    memory_log: MemoryAccessLog,
+
+    // Wall-clock limit of a single `Emulator::execute` call
+    timeout: Option<Duration>,
+
+    // Fails execution on suspected infinite loops, see `Executor::set_loop_detection`
+    loop_detector: Option<LoopDetector>,
+
+    // Per-opcode and per-region cycle counts, collected only if a profiler is attached
+    #[cfg(feature = "profiler")]
+    pub profiler: Option<Profiler>,
 }
 
 impl Executor {
@@ -216,6 +251,119 @@ impl Executor {
             self.logs = None;
         }
     }
+
+    /// Set whether DIV, DIVU, REM and REMU with a zero divisor fail with [`VMError::DivisionByZero`].
+    ///
+    /// Disabled by default, in which case division by zero follows the RISC-V specification.
+    pub fn trap_division_by_zero(&mut self, trap: bool) {
+        self.trap_division_by_zero = trap;
+    }
+
+    /// Set whether memory accesses of executed instructions are recorded in the memory access log.
+    ///
+    /// Disabled by default, the log is kept when recording is disabled again.
+    pub fn log_memory_accesses(&mut self, enable: bool) {
+        self.log_memory_accesses = enable;
+    }
+
+    /// Returns the memory accesses recorded so far.
+    pub fn memory_log(&self) -> &MemoryAccessLog {
+        &self.memory_log
+    }
+
+    /// Set the wall-clock limit of [`Emulator::execute`], after which it fails with [`VMError::Timeout`].
+    ///
+    /// The elapsed time is checked every 1024 instructions, at the end of a basic block. Requires a system clock,
+    /// without a timeout the emulator never reads the time.
+    pub fn set_timeout(&mut self, duration: Duration) {
+        self.timeout = Some(duration);
+    }
+
+    /// Set [`Emulator::execute`] to fail with [`VMError::SuspectedInfiniteLoop`] once a cycle of at most `window`
+    /// instructions is repeated more than `repetitions` times in a row, see [`LoopDetector`].
+    ///
+    /// Disabled by default. Panics if `window` or `repetitions` is zero.
+    pub fn set_loop_detection(&mut self, window: usize, repetitions: usize) {
+        self.loop_detector = Some(LoopDetector::new(window, repetitions));
+    }
+
+    /// Moves the PC past the instruction executed at `pc`, branches and jumps have already updated it.
+    #[cfg(not(feature = "riscv-c"))]
+    fn advance_pc(
+        &mut self,
+        _pc: u32,
+        bare_instruction: &Instruction,
+        _res: &mut InstructionResult,
+    ) {
+        if !bare_instruction.is_branch_or_jump_instruction() {
+            self.cpu.pc.step();
+        }
+    }
+
+    /// Moves the PC past the instruction executed at `pc`, branches and jumps have already updated it.
+    ///
+    /// Compressed jumps and branches are executed as their 32-bit equivalents, which link and fall
+    /// through to `pc + 4`. Their link address and fallthrough are corrected to the instruction
+    /// following them at `pc + 2`.
+    #[cfg(feature = "riscv-c")]
+    fn advance_pc(&mut self, pc: u32, bare_instruction: &Instruction, res: &mut InstructionResult) {
+        let size = self.instruction_size(pc);
+        match bare_instruction.opcode.builtin() {
+            Some(BuiltinOpcode::JAL | BuiltinOpcode::JALR) => {
+                if size == 2 {
+                    let next_pc = pc.wrapping_add(2);
+                    self.cpu.registers.write(bare_instruction.op_a, next_pc);
+                    *res = Some(next_pc);
+                }
+            }
+            // The only compressed branches are c.beqz and c.bnez. A taken branch may also land on
+            // `pc + 4`, so the condition is evaluated again, branches don't modify registers.
+            Some(opcode @ (BuiltinOpcode::BEQ | BuiltinOpcode::BNE)) if size == 2 => {
+                let equal = self.cpu.registers[bare_instruction.op_a]
+                    == self.cpu.registers[bare_instruction.op_b];
+                if equal != (opcode == BuiltinOpcode::BEQ) {
+                    let next_pc = pc.wrapping_add(2);
+                    self.cpu.pc.value = next_pc;
+                    *res = Some(next_pc);
+                }
+            }
+            _ if !bare_instruction.is_branch_or_jump_instruction() => {
+                self.cpu.pc.value = pc.wrapping_add(size);
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the size in bytes of the instruction at `pc` in the basic block cache, instructions outside of
+    /// it are assumed to be 32-bit.
+    #[cfg(feature = "riscv-c")]
+    fn instruction_size(&self, pc: u32) -> u32 {
+        self.basic_block_ref_cache
+            .get(&pc)
+            .and_then(|start| self.basic_block_cache.get(start))
+            .map_or(WORD_SIZE as u32, |entry| {
+                entry.instruction_size(entry.index_of(pc))
+            })
+    }
+}
+
+/// Decodes the basic block starting at `pc` from the instruction words following it.
+#[cfg(not(feature = "riscv-c"))]
+fn decode_basic_block(words: &[u32], pc: u32) -> BasicBlockEntry {
+    BasicBlockEntry::new(pc, decode_until_end_of_a_block(words))
+}
+
+/// Decodes the basic block starting at `pc` from the instruction words following it.
+///
+/// Instructions are fetched as 16-bit parcels, so `pc` may point to the upper half of the first word.
+#[cfg(feature = "riscv-c")]
+fn decode_basic_block(words: &[u32], pc: u32) -> BasicBlockEntry {
+    let parcels = words
+        .iter()
+        .flat_map(|word| [*word as u16, (word >> 16) as u16])
+        .skip((pc as usize % WORD_SIZE) / 2);
+    let (block, sizes) = decode_parcels_until_end_of_a_block(parcels);
+    BasicBlockEntry::with_sizes(pc, block, &sizes)
 }
 
 pub trait Emulator {
@@ -234,6 +382,25 @@ pub trait Emulator {
         bare_instruction: &Instruction,
         force_provable_transcript: bool,
     ) -> Result<(InstructionResult, (HashSet<LoadOp>, HashSet<StoreOp>))> {
+        if matches!(
+            bare_instruction.opcode.builtin(),
+            Some(BuiltinOpcode::ECALL)
+        ) {
+            let code = executor.cpu.registers[Register::X17];
+            if let Some(handler) = executor.syscalls.custom_handler(code) {
+                let mut state = SyscallState::new(&executor.cpu);
+                handler(&mut state)?;
+                let result = state.result();
+                if let Some((reg, value)) = result {
+                    executor.cpu.registers.write(reg, value);
+                }
+                return Ok((result.map(|(_, value)| value), Default::default()));
+            }
+            if !executor.syscalls.is_builtin(code) {
+                return Err(VMError::UnimplementedSyscall(code, executor.cpu.pc.value));
+            }
+        }
+
         let mut syscall_instruction = SyscallInstruction::decode(bare_instruction, &executor.cpu)?;
         let load_ops = syscall_instruction.memory_read(memory)?;
         syscall_instruction.execute(executor, memory, memory_layout, force_provable_transcript)?;
@@ -275,6 +442,11 @@ pub trait Emulator {
     /// Return a mutable reference to the internal executor component used by the emulator.
     fn get_executor_mut(&mut self) -> &mut Executor;
 
+    /// Return the memory accesses recorded so far, see [`Executor::log_memory_accesses`].
+    fn memory_log(&self) -> &MemoryAccessLog {
+        self.get_executor().memory_log()
+    }
+
     /// Execute an entire basic block.
     fn execute_basic_block(
         &mut self,
@@ -289,11 +461,20 @@ pub trait Emulator {
         let mut results: Vec<InstructionResult> = Vec::new();
         let mut transcript: MemoryTranscript = Vec::new();
 
-        let at = (self.get_executor().cpu.pc.value as usize - basic_block_entry.start as usize)
-            / WORD_SIZE;
+        let at = basic_block_entry.index_of(self.get_executor().cpu.pc.value);
 
         // Execute the instructions in the basic block
         for instruction in basic_block_entry.block.0[at..].iter() {
+            #[cfg(feature = "profiler")]
+            if let Some(profiler) = self.get_executor_mut().profiler.as_mut() {
+                profiler.record(&instruction.opcode);
+            }
+
+            let pc = self.get_executor().cpu.pc.value;
+            if let Some(loop_detector) = self.get_executor_mut().loop_detector.as_mut() {
+                loop_detector.record(pc)?;
+            }
+
             let (res, mem) = self.execute_instruction(instruction, force_provable_transcript)?;
             results.push(res);
             transcript.push(mem);
@@ -310,6 +491,15 @@ pub trait Emulator {
         let mut results: Vec<InstructionResult> = Vec::new();
         let mut transcript: MemoryTranscript = Vec::new();
 
+        // The clock is only read with a timeout set, `Instant::now` panics on targets without a system clock, such
+        // as wasm32-unknown-unknown.
+        let deadline = self
+            .get_executor()
+            .timeout
+            .map(|timeout| (Instant::now(), timeout));
+        let start_clock = self.get_executor().global_clock;
+        let mut next_timeout_check = start_clock + TIMEOUT_CHECK_INTERVAL;
+
         loop {
             let basic_block_entry = self.fetch_block(self.get_executor().cpu.pc.value)?;
             let (res, mem) =
@@ -317,6 +507,18 @@ pub trait Emulator {
 
             results.extend(res);
             transcript.extend(mem);
+
+            let clock = self.get_executor().global_clock;
+            if let Some((start_time, timeout)) = deadline.filter(|_| clock >= next_timeout_check) {
+                next_timeout_check = clock + TIMEOUT_CHECK_INTERVAL;
+                let elapsed = start_time.elapsed();
+                if elapsed > timeout {
+                    return Err(VMError::Timeout {
+                        elapsed_ns: elapsed.as_nanos() as u64,
+                        step_count: (clock - start_clock) as u64,
+                    });
+                }
+            }
         }
     }
 
@@ -411,6 +613,9 @@ pub struct HarvardEmulator {
 
     // Tracker for the memory sizes since they are not known ahead of time
     memory_stats: MemoryStats,
+
+    // The custom memory layout the program was linked for, if any
+    memory_layout: Option<MemoryLayout>,
 }
 
 impl Default for HarvardEmulator {
@@ -425,6 +630,7 @@ impl Default for HarvardEmulator {
             static_ram_image: BTreeMap::new(),
             data_memory: UnifiedMemory::default(),
             memory_stats: MemoryStats::default(),
+            memory_layout: None,
         }
     }
 }
@@ -522,11 +728,56 @@ impl HarvardEmulator {
             static_ram_image,
             data_memory,
             memory_stats: MemoryStats::new(data_end, MEMORY_TOP),
+            memory_layout: None,
         };
         emulator.executor.cpu.pc.value = emulator.executor.entrypoint;
         emulator
     }
 
+    /// Creates a HarvardEmulator from an ELF file linked for a custom memory layout.
+    ///
+    /// The stack pointer is initialized to the top of the stack segment, and the memory statistics
+    /// track heap and stack usage within the heap and stack segments of the layout. Loads and stores
+    /// outside the data, heap, and stack segments fail with
+    /// [`MemoryError::InvalidMemoryAccess`](crate::error::MemoryError::InvalidMemoryAccess).
+    ///
+    /// Returns [`VMError::InvalidMemoryLayout`] if the instructions of the ELF file do not fit in the
+    /// text segment, or its static data does not fit in the data segment.
+    pub fn from_elf_with_layout(
+        layout: &MemoryLayout,
+        elf: &ElfFile,
+        public_input: &[u8],
+        private_input: &[u8],
+    ) -> Result<Self> {
+        let text = layout.text();
+        let text_size = (elf.instructions.len() * WORD_SIZE) as u64;
+        if elf.base < text.start() || elf.base as u64 + text_size > text.end() as u64 {
+            return Err(VMError::InvalidMemoryLayout);
+        }
+        if !text.contains(elf.entry) {
+            return Err(VMError::InvalidMemoryLayout);
+        }
+        let data = layout.data();
+        if elf
+            .rom_image
+            .keys()
+            .chain(elf.ram_image.keys())
+            .any(|&addr| !data.contains(addr) || !data.contains(addr + WORD_SIZE as u32 - 1))
+        {
+            return Err(VMError::InvalidMemoryLayout);
+        }
+
+        let mut emulator = Self::from_elf(elf, public_input, private_input);
+        emulator
+            .executor
+            .cpu
+            .registers
+            .write(Register::X2, layout.stack_top());
+        emulator.memory_stats = MemoryStats::new(layout.heap().start(), layout.stack().end());
+        emulator.memory_layout = Some(*layout);
+        Ok(emulator)
+    }
+
     /// Creates a HarvardEmulator from a basic block IR, for simple testing purposes.
     ///
     /// This function initializes a Harvard with a single basic block of instructions.
@@ -555,6 +806,51 @@ impl HarvardEmulator {
         emulator.executor.cpu.pc.value = emulator.executor.entrypoint;
         emulator
     }
+
+    /// Captures the current state of the emulator, see [`VmSnapshot`].
+    ///
+    /// Debug logs and the cycle tracker are not part of the snapshot.
+    pub fn snapshot(&self) -> VmSnapshot {
+        VmSnapshot {
+            registers: self.executor.cpu.registers,
+            pc: self.executor.cpu.pc.value,
+            cycles: self.executor.cpu.cycles,
+            global_clock: self.executor.global_clock,
+            access_timestamps: self.executor.access_timestamps.clone(),
+            private_input_tape: self.executor.private_input_tape.clone(),
+            data_memory: self.data_memory.clone(),
+            output_memory: self.output_memory.clone(),
+            memory_stats: self.memory_stats.clone(),
+            memory_layout: self.memory_layout,
+        }
+    }
+
+    /// Resets the emulator to the state captured by [`HarvardEmulator::snapshot`].
+    ///
+    /// Returns [`VMError::SnapshotLayoutMismatch`] if the snapshot was taken from an emulator with a
+    /// different memory layout, in which case the emulator is left unchanged.
+    pub fn restore(&mut self, snap: VmSnapshot) -> Result<()> {
+        if snap.memory_layout != self.memory_layout {
+            return Err(VMError::SnapshotLayoutMismatch {
+                snapshot: snap.memory_layout,
+                emulator: self.memory_layout,
+            });
+        }
+
+        let cpu = &mut self.executor.cpu;
+        cpu.registers = snap.registers;
+        cpu.pc.value = snap.pc;
+        cpu.cycles = snap.cycles;
+        cpu.snapshot = (snap.registers, cpu.pc);
+        self.executor.global_clock = snap.global_clock;
+        self.executor.memory_log.truncate(snap.global_clock as u64);
+        self.executor.access_timestamps = snap.access_timestamps;
+        self.executor.private_input_tape = snap.private_input_tape;
+        self.data_memory = snap.data_memory;
+        self.output_memory = snap.output_memory;
+        self.memory_stats = snap.memory_stats;
+        Ok(())
+    }
 }
 
 impl Emulator for HarvardEmulator {
@@ -569,7 +865,15 @@ impl Emulator for HarvardEmulator {
         bare_instruction: &Instruction,
         force_provable_transcript: bool,
     ) -> Result<(InstructionResult, MemoryRecords)> {
-        let ((res, (load_ops, store_ops)), accessed_io_memory) = match (
+        if self.executor.trap_division_by_zero {
+            check_division_by_zero(&self.executor.cpu, bare_instruction)?;
+        }
+        #[cfg(not(feature = "emulate-atomics"))]
+        check_atomic_operation(&self.executor.cpu, bare_instruction)?;
+        check_memory_alignment(&self.executor.cpu, bare_instruction)?;
+        let pc = self.executor.cpu.pc.value;
+
+        let ((mut res, (load_ops, store_ops)), accessed_io_memory) = match (
             self.executor
                 .instruction_executor
                 .get_for_read_input(&bare_instruction.opcode),
@@ -614,6 +918,12 @@ impl Emulator for HarvardEmulator {
                 )?,
                 false,
             ),
+            (_, _, Err(VMError::UnimplementedInstruction { opcode, pc: None })) => {
+                return Err(VMError::UnimplementedInstruction {
+                    opcode,
+                    pc: Some(self.executor.cpu.pc.value),
+                })
+            }
             (_, _, Err(e)) => return Err(e),
         };
 
@@ -627,8 +937,19 @@ impl Emulator for HarvardEmulator {
             memory_records.insert(op.as_record(self.executor.global_clock));
         });
 
+        if self.executor.log_memory_accesses {
+            self.executor.memory_log.record(pc, &memory_records);
+        }
+
         // Update the memory size statistics.
         if !accessed_io_memory {
+            if let Some(layout) = &self.memory_layout {
+                load_ops
+                    .iter()
+                    .map(|op| (op.get_address(), op.get_size()))
+                    .chain(store_ops.iter().map(|op| (op.get_address(), op.get_size())))
+                    .try_for_each(|(address, size)| layout.check_access(address, size))?;
+            }
             self.memory_stats.update(
                 load_ops,
                 store_ops,
@@ -636,9 +957,7 @@ impl Emulator for HarvardEmulator {
             )?;
         }
 
-        if !bare_instruction.is_branch_or_jump_instruction() {
-            self.executor.cpu.pc.step();
-        }
+        self.executor.advance_pc(pc, bare_instruction, &mut res);
 
         // The global clock will update according to the currency of ZK (constraint?)
         // instead of pure RISC-V cycle count.
@@ -663,12 +982,11 @@ impl Emulator for HarvardEmulator {
             return Ok(self.executor.basic_block_cache.get(start).unwrap().clone());
         }
 
-        let block = decode_until_end_of_a_block(self.instruction_memory.segment(pc, None));
-        if block.is_empty() {
+        let entry = decode_basic_block(self.instruction_memory.segment(pc, None), pc);
+        if entry.block.is_empty() {
             return Err(VMError::VMOutOfInstructions);
         }
 
-        let entry = BasicBlockEntry::new(pc, block);
         let _ = self.executor.basic_block_cache.insert(pc, entry.clone());
 
         self.executor
@@ -1036,7 +1354,15 @@ impl Emulator for LinearEmulator {
         bare_instruction: &Instruction,
         _force_second_pass: bool, // Linear Emulator always does second pass
     ) -> Result<(InstructionResult, MemoryRecords)> {
-        let (res, (load_ops, store_ops)) = match (
+        if self.executor.trap_division_by_zero {
+            check_division_by_zero(&self.executor.cpu, bare_instruction)?;
+        }
+        #[cfg(not(feature = "emulate-atomics"))]
+        check_atomic_operation(&self.executor.cpu, bare_instruction)?;
+        check_memory_alignment(&self.executor.cpu, bare_instruction)?;
+        let pc = self.executor.cpu.pc.value;
+
+        let (mut res, (load_ops, store_ops)) = match (
             self.executor
                 .instruction_executor
                 .get_for_read_input(&bare_instruction.opcode),
@@ -1065,6 +1391,12 @@ impl Emulator for LinearEmulator {
             (_, _, Ok(executor)) => {
                 executor(&mut self.executor.cpu, &mut self.memory, bare_instruction)?
             }
+            (_, _, Err(VMError::UnimplementedInstruction { opcode, pc: None })) => {
+                return Err(VMError::UnimplementedInstruction {
+                    opcode,
+                    pc: Some(self.executor.cpu.pc.value),
+                })
+            }
             (_, _, Err(e)) => return Err(e),
         };
 
@@ -1078,10 +1410,12 @@ impl Emulator for LinearEmulator {
             memory_records.insert(op.as_record(self.executor.global_clock));
         });
 
-        if !bare_instruction.is_branch_or_jump_instruction() {
-            self.executor.cpu.pc.step();
+        if self.executor.log_memory_accesses {
+            self.executor.memory_log.record(pc, &memory_records);
         }
 
+        self.executor.advance_pc(pc, bare_instruction, &mut res);
+
         // The global clock will update according to the currency of ZK (constraint?)
         // instead of pure RISC-V cycle count.
         // Right now we don't have information how an instruction cost in ZK, so we just
@@ -1105,13 +1439,11 @@ impl Emulator for LinearEmulator {
             return Ok(self.executor.basic_block_cache.get(start).unwrap().clone());
         }
 
-        let block =
-            decode_until_end_of_a_block(self.memory.segment(self.instruction_index, pc, None)?);
-        if block.is_empty() {
+        let entry = decode_basic_block(self.memory.segment(self.instruction_index, pc, None)?, pc);
+        if entry.block.is_empty() {
             return Err(VMError::VMOutOfInstructions);
         }
 
-        let entry = BasicBlockEntry::new(pc, block);
         let _ = self.executor.basic_block_cache.insert(pc, entry.clone());
 
         self.executor
@@ -1279,6 +1611,8 @@ impl Emulator for LinearEmulator {
 mod tests {
     use super::*;
     use crate::elf::ElfFile;
+    use crate::error::MemoryError;
+    use crate::memory::ReadOrWrite;
     use crate::riscv::{BuiltinOpcode, Instruction, Opcode};
     use serial_test::serial;
 
@@ -1415,5 +1749,441 @@ mod tests {
         let res = emulator.execute_basic_block(&basic_block_entry, false);
 
         assert_eq!(res, Err(VMError::UndefinedInstruction(op)));
+
+        let unimpl = Opcode::from(BuiltinOpcode::UNIMPL);
+        let basic_block_entry = BasicBlockEntry::new(
+            0,
+            BasicBlock::new(vec![
+                Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 1, 0, 1),
+                Instruction::new_ir(unimpl.clone(), 0, 0, 0),
+            ]),
+        );
+        let mut emulator = HarvardEmulator::default();
+        let err = emulator
+            .execute_basic_block(&basic_block_entry, false)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unimplemented instruction \"unimpl\" at pc=0x00000004"
+        );
+
+        let mut emulator = LinearEmulator::default();
+        let res = emulator.execute_basic_block(&basic_block_entry, false);
+        assert_eq!(
+            res.map(|_| ()),
+            Err(VMError::UnimplementedInstruction {
+                opcode: unimpl,
+                pc: Some(4),
+            })
+        );
+    }
+
+    #[test]
+    fn test_execution_timeout() {
+        // An infinite loop: four additions followed by a jump back to the first one.
+        let mut instructions =
+            vec![Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 1, 1, 1); 4];
+        instructions.push(Instruction::new_ir(
+            Opcode::from(BuiltinOpcode::JAL),
+            0,
+            0,
+            (-4 * WORD_SIZE as i32) as u32,
+        ));
+        let mut emulator = HarvardEmulator::from_basic_blocks(&vec![BasicBlock::new(instructions)]);
+
+        let timeout = Duration::from_millis(50);
+        emulator.get_executor_mut().set_timeout(timeout);
+        let start = Instant::now();
+        let result = emulator.execute(false);
+        let elapsed = start.elapsed();
+
+        let Err(VMError::Timeout {
+            elapsed_ns,
+            step_count,
+        }) = result
+        else {
+            panic!("expected a timeout, got {result:?}");
+        };
+        assert!(elapsed_ns >= timeout.as_nanos() as u64);
+        assert!(step_count >= TIMEOUT_CHECK_INTERVAL as u64);
+        assert!(elapsed < 2 * timeout);
+    }
+
+    #[test]
+    fn test_suspected_infinite_loop() {
+        let basic_block = BasicBlock::new(vec![
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 1, 1, 1),
+            Instruction::new_ir(Opcode::from(BuiltinOpcode::JAL), 0, 0, (-4i32) as u32),
+        ]);
+        let mut emulator = HarvardEmulator::from_basic_blocks(&vec![basic_block]);
+        emulator
+            .executor
+            .set_loop_detection(LoopDetector::DEFAULT_WINDOW, 50);
+
+        assert_eq!(
+            emulator.execute(false),
+            Err(VMError::SuspectedInfiniteLoop {
+                cycle_pcs: vec![ELF_TEXT_START + 4, ELF_TEXT_START]
+            })
+        );
+        assert!(emulator.executor.global_clock < 200);
+    }
+
+    #[test]
+    fn test_misaligned_memory_access() {
+        let lw = Instruction::new_ir(Opcode::from(BuiltinOpcode::LW), 2, 1, 2);
+        let basic_block_entry = BasicBlockEntry::new(
+            0,
+            BasicBlock::new(vec![
+                Instruction::new_ir(Opcode::from(BuiltinOpcode::LUI), 1, 0, 0x80),
+                lw,
+            ]),
+        );
+        let expected = Err(VMError::InvalidAlignment {
+            addr: 0x80002,
+            required_alignment: 4,
+            pc: 4,
+        });
+
+        let mut emulator = HarvardEmulator::default();
+        let res = emulator.execute_basic_block(&basic_block_entry, false);
+        assert_eq!(res.map(|_| ()), expected);
+
+        let mut emulator = LinearEmulator::default();
+        let res = emulator.execute_basic_block(&basic_block_entry, false);
+        assert_eq!(res.map(|_| ()), expected);
+    }
+
+    #[test]
+    #[cfg(feature = "riscv-c")]
+    fn test_harvard_compressed_instructions() {
+        let parcels: [u16; 8] = [
+            0x4515, // 0x00: c.li a0, 5
+            0x0593, // 0x02: addi a1, x0, 7, straddling a word boundary
+            0x0070, 0x952e, // 0x06: c.add a0, a1
+            0x2011, // 0x08: c.jal 4
+            0x4501, // 0x0a: c.li a0, 0, skipped
+            0x8606, // 0x0c: c.mv a2, ra
+            0x0605, // 0x0e: c.addi a2, 1
+        ];
+        let words: Vec<u32> = parcels
+            .chunks(2)
+            .map(|parcel| parcel[0] as u32 | ((parcel[1] as u32) << 16))
+            .collect();
+
+        let mut emulator = HarvardEmulator {
+            instruction_memory: FixedMemory::<RO>::from_vec(0, words.len() * WORD_SIZE, words),
+            data_memory: UnifiedMemory::from(VariableMemory::<RW>::default()),
+            ..Default::default()
+        };
+        assert_eq!(emulator.execute(false), Err(VMError::VMOutOfInstructions));
+
+        let registers = &emulator.executor.cpu.registers;
+        assert_eq!(registers[Register::X10], 12);
+        assert_eq!(registers[Register::X11], 7);
+        // c.jal links to the instruction following it, two bytes after the jump.
+        assert_eq!(registers[Register::X1], 0x0a);
+        assert_eq!(registers[Register::X12], 0x0b);
+        assert_eq!(emulator.executor.cpu.pc.value, 0x10);
+    }
+
+    #[test]
+    #[cfg(feature = "riscv-c")]
+    fn test_harvard_compressed_branches() {
+        let parcels: [u16; 6] = [
+            0x4501, // 0x00: c.li a0, 0
+            0xe111, // 0x02: c.bnez a0, 4, not taken
+            0x4585, // 0x04: c.li a1, 1
+            0xc111, // 0x06: c.beqz a0, 4, taken
+            0x460d, // 0x08: c.li a2, 3, skipped
+            0x4609, // 0x0a: c.li a2, 2
+        ];
+        let words: Vec<u32> = parcels
+            .chunks(2)
+            .map(|parcel| parcel[0] as u32 | ((parcel[1] as u32) << 16))
+            .collect();
+
+        let mut emulator = HarvardEmulator {
+            instruction_memory: FixedMemory::<RO>::from_vec(0, words.len() * WORD_SIZE, words),
+            data_memory: UnifiedMemory::from(VariableMemory::<RW>::default()),
+            ..Default::default()
+        };
+        assert_eq!(emulator.execute(false), Err(VMError::VMOutOfInstructions));
+
+        // A branch that isn't taken falls through to the instruction two bytes after it.
+        let registers = &emulator.executor.cpu.registers;
+        assert_eq!(registers[Register::X11], 1);
+        assert_eq!(registers[Register::X12], 2);
+        assert_eq!(emulator.executor.cpu.pc.value, 0x0c);
+    }
+
+    #[test]
+    fn test_fence_is_noop() {
+        // fence rw, rw
+        let fence = crate::riscv::decode_instruction(0x0330_000f);
+        assert_eq!(fence.opcode, Opcode::from(BuiltinOpcode::FENCE));
+
+        let basic_block_entry = BasicBlockEntry::new(
+            0,
+            BasicBlock::new(vec![
+                Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 1, 0, 5),
+                fence,
+                Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 2, 1, 1),
+            ]),
+        );
+        let mut emulator = HarvardEmulator::default();
+        emulator
+            .execute_basic_block(&basic_block_entry, false)
+            .unwrap();
+
+        assert_eq!(emulator.executor.cpu.registers[Register::X1], 5);
+        assert_eq!(emulator.executor.cpu.registers[Register::X2], 6);
+        assert_eq!(emulator.executor.cpu.pc.value, 3 * WORD_SIZE as u32);
+    }
+
+    #[test]
+    #[serial]
+    fn test_harvard_from_elf_with_layout() {
+        let elf_file = ElfFile::from_path("test/fib_10.elf").expect("Unable to load ELF file");
+        let text_size = (elf_file.instructions.len() * WORD_SIZE) as u32;
+        let data_start = elf_file.base + text_size;
+        let data_end = elf_file
+            .rom_image
+            .keys()
+            .chain(elf_file.ram_image.keys())
+            .max()
+            .map_or(data_start, |addr| addr + WORD_SIZE as u32);
+        // the runtime of the guest sets up its stack pointer right below 0x8040_0000
+        let builder = MemoryLayoutBuilder::new()
+            .text(elf_file.base, text_size)
+            .stack(0x8000_0000, 0x40_0000);
+
+        // static data does not fit in the layout
+        let layout = builder.build().unwrap();
+        let res = HarvardEmulator::from_elf_with_layout(&layout, &elf_file, &[], &[]);
+        assert_eq!(res.map(|_| ()), Err(VMError::InvalidMemoryLayout));
+
+        let layout = builder
+            .data(data_start, data_end - data_start)
+            .heap(data_end, 0x10_0000)
+            .build()
+            .unwrap();
+        let mut emulator =
+            HarvardEmulator::from_elf_with_layout(&layout, &elf_file, &[], &[]).unwrap();
+        assert_eq!(
+            emulator.executor.cpu.registers[Register::X2],
+            layout.stack_top()
+        );
+        assert_eq!(emulator.execute(false), Err(VMError::VMExited(0)));
+
+        // the stack of the guest doesn't fit in a smaller stack segment
+        let layout = builder
+            .data(data_start, data_end - data_start)
+            .heap(data_end, 0x10_0000)
+            .stack(0x8000_0000, 0x10_0000)
+            .build()
+            .unwrap();
+        let mut emulator =
+            HarvardEmulator::from_elf_with_layout(&layout, &elf_file, &[], &[]).unwrap();
+        assert!(matches!(
+            emulator.execute(false),
+            Err(VMError::MemoryError(MemoryError::InvalidMemoryAccess(_)))
+        ));
+    }
+
+    #[test]
+    fn test_harvard_memory_layout_enforced() {
+        let layout = MemoryLayoutBuilder::new()
+            .text(0x1000, 0x1000)
+            .data(0x2000, 0x1000)
+            .stack(0x10000, 0x1000)
+            .build()
+            .unwrap();
+        let basic_block_entry = BasicBlockEntry::new(
+            0,
+            BasicBlock::new(vec![
+                Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 1, 0, 42),
+                Instruction::new_ir(Opcode::from(BuiltinOpcode::SW), 0, 1, 0x2ffc),
+                Instruction::new_ir(Opcode::from(BuiltinOpcode::LW), 2, 0, 0x2ffc),
+                Instruction::new_ir(Opcode::from(BuiltinOpcode::SW), 0, 1, 0x3000),
+            ]),
+        );
+        let mut emulator = HarvardEmulator {
+            data_memory: UnifiedMemory::from(VariableMemory::<RW>::default()),
+            memory_layout: Some(layout),
+            ..Default::default()
+        };
+
+        // the store right past the data segment is rejected
+        assert_eq!(
+            emulator.execute_basic_block(&basic_block_entry, false),
+            Err(VMError::MemoryError(MemoryError::InvalidMemoryAccess(
+                0x3000
+            )))
+        );
+        assert_eq!(emulator.executor.cpu.registers[Register::X2], 42);
+        assert_eq!(emulator.executor.cpu.pc.value, 12);
+    }
+
+    #[test]
+    fn test_harvard_snapshot_restore() {
+        let basic_block_entry = |start, value| {
+            BasicBlockEntry::new(
+                start,
+                BasicBlock::new(vec![
+                    Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 1, 1, value),
+                    Instruction::new_ir(Opcode::from(BuiltinOpcode::SW), 0, 1, 0x1000),
+                ]),
+            )
+        };
+
+        let mut emulator = HarvardEmulator {
+            data_memory: UnifiedMemory::from(VariableMemory::<RW>::default()),
+            ..Default::default()
+        };
+        emulator
+            .execute_basic_block(&basic_block_entry(0, 5), false)
+            .unwrap();
+        let snapshot = emulator.snapshot();
+        assert_eq!(snapshot.pc(), 8);
+        assert_eq!(snapshot.registers()[Register::X1], 5);
+
+        emulator
+            .execute_basic_block(&basic_block_entry(8, 7), false)
+            .unwrap();
+        assert_eq!(emulator.executor.cpu.registers[Register::X1], 12);
+        assert_ne!(emulator.snapshot(), snapshot);
+
+        emulator.restore(snapshot.clone()).unwrap();
+        assert_eq!(emulator.snapshot(), snapshot);
+        assert_eq!(emulator.executor.cpu.pc.value, 8);
+        let load = emulator.data_memory.read(0x1000, MemAccessSize::Word);
+        assert_eq!(load.unwrap().get_value(), 5);
+
+        // snapshots can't be restored into an emulator with a different layout
+        let layout = MemoryLayoutBuilder::new()
+            .text(0x1000, 0x1000)
+            .stack(0x10000, 0x1000)
+            .build()
+            .unwrap();
+        let mut emulator = HarvardEmulator {
+            memory_layout: Some(layout),
+            ..Default::default()
+        };
+        assert_eq!(
+            emulator.restore(snapshot),
+            Err(VMError::SnapshotLayoutMismatch {
+                snapshot: None,
+                emulator: Some(layout),
+            })
+        );
+    }
+
+    #[test]
+    fn test_memory_log() {
+        let basic_block_entry = BasicBlockEntry::new(
+            0,
+            BasicBlock::new(vec![
+                Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 1, 0, 42),
+                Instruction::new_ir(Opcode::from(BuiltinOpcode::SW), 0, 1, 0x100),
+                Instruction::new_ir(Opcode::from(BuiltinOpcode::LB), 2, 0, 0x100),
+            ]),
+        );
+
+        let new_emulator = || HarvardEmulator {
+            data_memory: UnifiedMemory::from(VariableMemory::<RW>::default()),
+            ..Default::default()
+        };
+
+        let mut emulator = new_emulator();
+        emulator
+            .execute_basic_block(&basic_block_entry, false)
+            .unwrap();
+        assert!(emulator.memory_log().is_empty());
+
+        let mut emulator = new_emulator();
+        emulator.get_executor_mut().log_memory_accesses(true);
+        emulator
+            .execute_basic_block(&basic_block_entry, false)
+            .unwrap();
+        let accesses: Vec<_> = emulator
+            .memory_log()
+            .iter()
+            .map(|access| (access.pc, access.addr, access.value, access.kind))
+            .collect();
+        assert_eq!(
+            accesses,
+            [
+                (4, 0x100, 42, ReadOrWrite::Write),
+                (8, 0x100, 42, ReadOrWrite::Read),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_custom_syscall() {
+        let basic_block_entry = BasicBlockEntry::new(
+            0,
+            BasicBlock::new(vec![
+                Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 10, 0, 2),
+                Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 11, 0, 3),
+                Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 17, 0, 0x500),
+                Instruction::new_ir(Opcode::from(BuiltinOpcode::ECALL), 0, 0, 0),
+            ]),
+        );
+
+        let mut emulator = HarvardEmulator::default();
+        let res = emulator.execute_basic_block(&basic_block_entry, false);
+        assert_eq!(
+            res.map(|_| ()),
+            Err(VMError::UnimplementedSyscall(0x500, 12))
+        );
+
+        let mut emulator = HarvardEmulator::default();
+        emulator
+            .get_executor_mut()
+            .syscalls
+            .register(0x500, |state| {
+                state.write_result(Register::X10, state.arg(0) * state.arg(1));
+                Ok(())
+            })
+            .unwrap();
+        emulator
+            .execute_basic_block(&basic_block_entry, false)
+            .unwrap();
+        assert_eq!(emulator.executor.cpu.registers[Register::X10], 6);
+    }
+
+    #[test]
+    fn test_division_by_zero() {
+        let divu = Instruction::new_ir(Opcode::from(BuiltinOpcode::DIVU), 3, 1, 2);
+        let basic_block_entry = BasicBlockEntry::new(
+            0,
+            BasicBlock::new(vec![
+                Instruction::new_ir(Opcode::from(BuiltinOpcode::ADDI), 1, 0, 20),
+                divu.clone(),
+            ]),
+        );
+        let expected = Err(VMError::DivisionByZero {
+            pc: 4,
+            opcode: divu.encode(),
+        });
+
+        // Division by zero is well-defined unless trapping is enabled.
+        let mut emulator = HarvardEmulator::default();
+        emulator
+            .execute_basic_block(&basic_block_entry, false)
+            .unwrap();
+        assert_eq!(emulator.executor.cpu.registers[3.into()], u32::MAX);
+
+        let mut emulator = HarvardEmulator::default();
+        emulator.get_executor_mut().trap_division_by_zero(true);
+        let res = emulator.execute_basic_block(&basic_block_entry, false);
+        assert_eq!(res.map(|_| ()), expected);
+
+        let mut emulator = LinearEmulator::default();
+        emulator.get_executor_mut().trap_division_by_zero(true);
+        let res = emulator.execute_basic_block(&basic_block_entry, false);
+        assert_eq!(res.map(|_| ()), expected);
     }
 }
@@ -0,0 +1,151 @@
+//! Heuristic detection of infinite loops.
+//!
+//! A `LoopDetector`, enabled with [`Executor::set_loop_detection`](super::Executor::set_loop_detection), keeps
+//! the program counters of the most recently executed instructions. Execution fails with
+//! [`VMError::SuspectedInfiniteLoop`] once the same sequence of program counters is executed more than a
+//! configured number of times in a row.
+//!
+//! Only the control flow is taken into account, so a loop running for many iterations before it exits
+//! is reported as well. The number of repetitions should be chosen well above the iteration count of
+//! loops expected in the guest program.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use nexus_vm::elf::ElfFile;
+//! use nexus_vm::emulator::{Emulator, HarvardEmulator, LoopDetector};
+//! use nexus_vm::error::VMError;
+//!
+//! let elf_file = ElfFile::from_path("test/fib_10.elf").unwrap();
+//! let mut emulator = HarvardEmulator::from_elf(&elf_file, &[], &[]);
+//! emulator
+//!     .executor
+//!     .set_loop_detection(LoopDetector::DEFAULT_WINDOW, 10_000);
+//! assert_eq!(emulator.execute(false), Err(VMError::VMExited(0)));
+//! ```
+use std::collections::VecDeque;
+
+use crate::error::{Result, VMError};
+
+#[derive(Debug, Clone)]
+pub struct LoopDetector {
+    // Maximum length of a detected cycle, and the number of program counters kept
+    window: usize,
+    // Number of repetitions of a cycle in a row after which execution fails
+    repetitions: usize,
+    // The most recent program counters, oldest first
+    history: VecDeque<u32>,
+    // Length of the cycle currently being repeated, 0 if none
+    period: usize,
+    // Number of program counters in a row that repeated the current cycle
+    matched: usize,
+}
+
+impl LoopDetector {
+    /// Number of program counters kept by default.
+    pub const DEFAULT_WINDOW: usize = 64;
+
+    /// Returns a detector of cycles of at most `window` instructions repeated more than `repetitions`
+    /// times in a row.
+    ///
+    /// Panics if `window` or `repetitions` is zero.
+    pub fn new(window: usize, repetitions: usize) -> Self {
+        assert!(window > 0, "loop detection window must not be empty");
+        assert!(
+            repetitions > 0,
+            "loop detection requires at least one repetition"
+        );
+        Self {
+            window,
+            repetitions,
+            history: VecDeque::with_capacity(window),
+            period: 0,
+            matched: 0,
+        }
+    }
+
+    /// Records the execution of the instruction at `pc`.
+    ///
+    /// Returns [`VMError::SuspectedInfiniteLoop`] with the program counters of the repeated cycle, in
+    /// execution order, once the cycle is repeated too many times.
+    pub fn record(&mut self, pc: u32) -> Result<()> {
+        let len = self.history.len();
+        if self.period > 0 && self.history[len - self.period] == pc {
+            self.matched += 1;
+        } else {
+            // Start over with the shortest cycle closed by `pc`, if any.
+            self.period = self
+                .history
+                .iter()
+                .rev()
+                .position(|&prev| prev == pc)
+                .map_or(0, |idx| idx + 1);
+            self.matched = self.period.min(1);
+        }
+
+        if len == self.window {
+            self.history.pop_front();
+        }
+        self.history.push_back(pc);
+
+        if self.period > 0 && self.matched > self.period * self.repetitions {
+            let len = self.history.len();
+            return Err(VMError::SuspectedInfiniteLoop {
+                cycle_pcs: self.history.range(len - self.period..).copied().collect(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_repeated_cycle() {
+        let mut detector = LoopDetector::new(LoopDetector::DEFAULT_WINDOW, 3);
+        // Straight-line code followed by a cycle of three instructions.
+        for pc in [0x00, 0x04, 0x08] {
+            detector.record(pc).unwrap();
+        }
+        let cycle = [0x0c, 0x10, 0x14];
+        let mut result = Ok(());
+        let mut steps = 0;
+        for pc in cycle.iter().cycle() {
+            result = detector.record(*pc);
+            steps += 1;
+            if result.is_err() {
+                break;
+            }
+        }
+
+        // The cycle is executed once, then repeated three times, and detected on the next instruction.
+        assert_eq!(steps, 3 * 4 + 1);
+        assert_eq!(
+            result,
+            Err(VMError::SuspectedInfiniteLoop {
+                cycle_pcs: vec![0x10, 0x14, 0x0c]
+            })
+        );
+    }
+
+    #[test]
+    fn test_interrupted_cycle_is_not_detected() {
+        let mut detector = LoopDetector::new(LoopDetector::DEFAULT_WINDOW, 2);
+        for idx in 0..10 {
+            // Two iterations of a loop, followed by code never executed again.
+            for pc in [0x00, 0x04, 0x00, 0x04, 0x100 + idx * 4] {
+                detector.record(pc).unwrap();
+            }
+        }
+
+        // Cycles longer than the window are never detected.
+        let mut detector = LoopDetector::new(4, 1);
+        for _ in 0..10 {
+            for pc in (0..5).map(|idx| idx * 4) {
+                detector.record(pc).unwrap();
+            }
+        }
+    }
+}
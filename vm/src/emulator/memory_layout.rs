@@ -0,0 +1,315 @@
+//! User-configurable memory layout for the Harvard emulator.
+//!
+//! Unlike [`LinearMemoryLayout`](super::LinearMemoryLayout), which derives every segment from the
+//! sizes of the program and its inputs, a `MemoryLayout` is described by explicit base addresses
+//! and sizes of the text, data, heap, and stack segments. This allows running programs linked for
+//! non-standard address spaces.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use nexus_vm::emulator::MemoryLayoutBuilder;
+//!
+//! let layout = MemoryLayoutBuilder::new()
+//!     .text(0x1000, 0x10000)
+//!     .data(0x11000, 0x1000)
+//!     .heap(0x12000, 0x100000)
+//!     .stack(0x200000, 0x100000)
+//!     .build()
+//!     .unwrap();
+//!
+//! assert_eq!(layout.stack().end(), 0x300000);
+//!
+//! // overlapping segments are rejected
+//! assert!(MemoryLayoutBuilder::new()
+//!     .text(0x1000, 0x10000)
+//!     .stack(0x8000, 0x1000)
+//!     .build()
+//!     .is_err());
+//! ```
+//!
+//! # Validation
+//!
+//! - Base addresses and sizes must be word-aligned.
+//! - Every segment must fit in the 32-bit address space, i.e. `start + size <= u32::MAX`.
+//! - Segments must not overlap each other, nor the reserved low addresses below `0x88`
+//!   that hold the registers and the public input/output start locations.
+//! - Text and stack segments are mandatory and must be non-empty, data and heap segments may be omitted.
+//!
+//! # Enforcement
+//!
+//! An emulator created with a layout fails with [`MemoryError::InvalidMemoryAccess`] on any load or store outside
+//! the data, heap, and stack segments, except for reads of the public input/output start locations.
+use crate::{
+    error::{MemoryError, Result, VMError},
+    memory::MemAccessSize,
+};
+use nexus_common::constants::{NUM_REGISTERS, WORD_SIZE};
+use serde::{Deserialize, Serialize};
+
+/// First address available to memory segments, lower addresses are reserved for the registers and
+/// the public input/output start locations.
+const RESERVED_END: u32 = (NUM_REGISTERS + 2) * WORD_SIZE as u32;
+
+/// A contiguous range of memory `[start, start + size)`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemorySegment {
+    start: u32,
+    size: u32,
+}
+
+impl MemorySegment {
+    pub const fn start(&self) -> u32 {
+        self.start
+    }
+
+    pub const fn size(&self) -> u32 {
+        self.size
+    }
+
+    /// Points to one byte past the end of the segment.
+    pub const fn end(&self) -> u32 {
+        self.start + self.size
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    pub const fn contains(&self, address: u32) -> bool {
+        self.start <= address && address < self.end()
+    }
+
+    fn validate(&self) -> Result<()> {
+        let word_mask = WORD_SIZE as u32 - 1;
+        if self.start & word_mask != 0 || self.size & word_mask != 0 {
+            return Err(VMError::InvalidMemoryLayout);
+        }
+        if self.start.checked_add(self.size).is_none() {
+            return Err(VMError::InvalidMemoryLayout);
+        }
+        if !self.is_empty() && self.start < RESERVED_END {
+            return Err(VMError::InvalidMemoryLayout);
+        }
+        Ok(())
+    }
+}
+
+/// Validated memory layout, constructed with [`MemoryLayoutBuilder`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryLayout {
+    text: MemorySegment,
+    data: MemorySegment,
+    heap: MemorySegment,
+    stack: MemorySegment,
+}
+
+impl MemoryLayout {
+    pub const fn text(&self) -> MemorySegment {
+        self.text
+    }
+
+    pub const fn data(&self) -> MemorySegment {
+        self.data
+    }
+
+    pub const fn heap(&self) -> MemorySegment {
+        self.heap
+    }
+
+    pub const fn stack(&self) -> MemorySegment {
+        self.stack
+    }
+
+    /// Points to the last accessible word of the stack, the initial value of the stack pointer.
+    pub const fn stack_top(&self) -> u32 {
+        self.stack.end() - WORD_SIZE as u32
+    }
+
+    /// Returns [`MemoryError::InvalidMemoryAccess`] unless all `size` bytes at `address` are within the data, heap
+    /// or stack segment, or the public input/output start locations.
+    pub(crate) fn check_access(&self, address: u32, size: MemAccessSize) -> Result<()> {
+        let io_locations = MemorySegment {
+            start: NUM_REGISTERS * WORD_SIZE as u32,
+            size: RESERVED_END - NUM_REGISTERS * WORD_SIZE as u32,
+        };
+        let last = address.checked_add(size as u32 - 1);
+        let in_bounds = [self.data, self.heap, self.stack, io_locations]
+            .iter()
+            .any(|segment| {
+                segment.contains(address) && last.is_some_and(|last| segment.contains(last))
+            });
+        if !in_bounds {
+            return Err(MemoryError::InvalidMemoryAccess(address).into());
+        }
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.text.is_empty() || self.stack.is_empty() {
+            return Err(VMError::InvalidMemoryLayout);
+        }
+
+        let mut segments = [self.text, self.data, self.heap, self.stack];
+        for segment in &segments {
+            segment.validate()?;
+        }
+
+        // Sort by start address, so that any overlap happens between neighbours.
+        segments.sort_by_key(|segment| segment.start);
+        let mut segments = segments.iter().filter(|segment| !segment.is_empty());
+        let mut prev = segments.next().expect("text segment is non-empty");
+        for segment in segments {
+            if prev.end() > segment.start {
+                return Err(VMError::InvalidMemoryLayout);
+            }
+            prev = segment;
+        }
+
+        Ok(())
+    }
+}
+
+/// Builder of [`MemoryLayout`], segments that are not set are empty.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MemoryLayoutBuilder {
+    text: MemorySegment,
+    data: MemorySegment,
+    heap: MemorySegment,
+    stack: MemorySegment,
+}
+
+impl MemoryLayoutBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the segment holding the program instructions.
+    pub fn text(mut self, start: u32, size: u32) -> Self {
+        self.text = MemorySegment { start, size };
+        self
+    }
+
+    /// Sets the segment holding the static read-only and read-write data.
+    pub fn data(mut self, start: u32, size: u32) -> Self {
+        self.data = MemorySegment { start, size };
+        self
+    }
+
+    pub fn heap(mut self, start: u32, size: u32) -> Self {
+        self.heap = MemorySegment { start, size };
+        self
+    }
+
+    /// Sets the stack segment, the stack grows downwards from `start + size`.
+    pub fn stack(mut self, start: u32, size: u32) -> Self {
+        self.stack = MemorySegment { start, size };
+        self
+    }
+
+    /// Returns the validated layout, or [`VMError::InvalidMemoryLayout`] if any segment is
+    /// misaligned, exceeds the 32-bit address space, or overlaps with another segment.
+    pub fn build(self) -> Result<MemoryLayout> {
+        let layout = MemoryLayout {
+            text: self.text,
+            data: self.data,
+            heap: self.heap,
+            stack: self.stack,
+        };
+        layout.validate()?;
+
+        Ok(layout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn builder() -> MemoryLayoutBuilder {
+        MemoryLayoutBuilder::new()
+            .text(0x1000, 0x1000)
+            .data(0x2000, 0x1000)
+            .heap(0x3000, 0x1000)
+            .stack(0x10000, 0x1000)
+    }
+
+    #[test]
+    fn test_build_memory_layout() {
+        let layout = builder().build().unwrap();
+        assert_eq!(layout.text().start(), 0x1000);
+        assert_eq!(layout.data().end(), 0x3000);
+        assert_eq!(layout.stack_top(), 0x10ffc);
+        assert!(layout.heap().contains(0x3ffc));
+        assert!(!layout.heap().contains(0x4000));
+
+        // optional segments may be omitted, and segments may be placed in any order
+        let layout = MemoryLayoutBuilder::new()
+            .stack(0x1000, 0x1000)
+            .text(0xfff0_0000, 0x1000)
+            .build()
+            .unwrap();
+        assert!(layout.data().is_empty());
+        assert!(layout.heap().is_empty());
+
+        // the last segment may end at the top of the address space
+        assert!(builder().stack(0xffff_f000, 0xffc).build().is_ok());
+    }
+
+    #[test]
+    fn test_check_access() {
+        let layout = builder().build().unwrap();
+        for (address, size) in [
+            (0x2000, MemAccessSize::Word),
+            (0x3ffc, MemAccessSize::Word),
+            (0x10fff, MemAccessSize::Byte),
+            (0x84, MemAccessSize::Word),
+        ] {
+            assert_eq!(layout.check_access(address, size), Ok(()));
+        }
+        for (address, size) in [
+            // text segment
+            (0x1000, MemAccessSize::Word),
+            // past the heap, and across the end of the stack
+            (0x4000, MemAccessSize::Byte),
+            (0x10ffe, MemAccessSize::Word),
+            // registers
+            (0x7c, MemAccessSize::Word),
+        ] {
+            assert_eq!(
+                layout.check_access(address, size),
+                Err(VMError::MemoryError(MemoryError::InvalidMemoryAccess(
+                    address
+                ))),
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_memory_layout() {
+        let invalid = [
+            // missing mandatory segments
+            MemoryLayoutBuilder::new().text(0x1000, 0x1000),
+            MemoryLayoutBuilder::new().stack(0x1000, 0x1000),
+            // misaligned start or size
+            builder().heap(0x3002, 0x1000),
+            builder().data(0x2000, 0x0fff),
+            // overlapping segments
+            builder().data(0x1ffc, 0x1000),
+            builder().stack(0x800, 0x1000),
+            builder().heap(0x3000, 0x10000),
+            // reserved low addresses
+            builder().text(0x84, 0x1000),
+            // exceeding the 32-bit address space
+            builder().stack(0xffff_f000, 0x1000),
+            builder().stack(0xffff_f000, 0xffff_f000),
+        ];
+        for builder in invalid {
+            assert_eq!(
+                builder.build(),
+                Err(VMError::InvalidMemoryLayout),
+                "{builder:?}"
+            );
+        }
+    }
+}
@@ -41,7 +41,11 @@ use crate::{
 use std::cmp::{max, min};
 use std::collections::HashSet;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "snapshot-serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
 pub struct MemoryStats {
     pub max_heap_access: u32,
     pub min_stack_access: u32,
@@ -9,6 +9,10 @@
 //! - `HarvardEmulator`: An implementation of the emulator using Harvard architecture.
 //! - `LinearEmulator`: An implementation of the emulator using Linear architecture.
 //! - `LinearMemoryLayout`: Defines the memory layout for the linear emulator.
+//! - `MemoryLayout`: A custom memory layout for the Harvard emulator, built with `MemoryLayoutBuilder`.
+//! - `LoopDetector`: Heuristic detection of infinite loops, enabled on the `Executor`.
+//! - `VmSnapshot`: A checkpoint of the Harvard emulator state, see `HarvardEmulator::snapshot`.
+//! - `Profiler`: Per-opcode and per-region cycle counts, requires the `profiler` feature.
 //!
 //! ## Memory Management
 //!
@@ -54,11 +58,22 @@
 //! visibility into the emulator's state and execution results.
 mod executor;
 mod layout;
+mod loop_detector;
+mod memory_layout;
 mod memory_stats;
+#[cfg(feature = "profiler")]
+mod profiler;
 mod registry;
+mod snapshot;
 
 pub use executor::{Emulator, Executor, HarvardEmulator, LinearEmulator};
 pub use layout::LinearMemoryLayout;
+pub use loop_detector::LoopDetector;
+pub use memory_layout::{MemoryLayout, MemoryLayoutBuilder, MemorySegment};
+pub use snapshot::VmSnapshot;
+
+#[cfg(feature = "profiler")]
+pub use profiler::Profiler;
 
 mod utils;
 pub use utils::*;
@@ -0,0 +1,174 @@
+//! Instruction-level cycle profiling.
+//!
+//! A `Profiler` attached to the [`Executor`](super::Executor) counts executed instructions per opcode,
+//! every instruction accounts for a single cycle. Regions delimited by the profile labels of the cycle
+//! count syscall ("^#name" and "$#name") are tracked as well, nested regions are reported with the path
+//! of their enclosing regions, e.g. "main/fib".
+//!
+//! Only available with the `profiler` feature.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use nexus_vm::elf::ElfFile;
+//! use nexus_vm::emulator::{Emulator, HarvardEmulator, Profiler};
+//!
+//! let elf_file = ElfFile::from_path("test/fib_10.elf").unwrap();
+//! let mut emulator = HarvardEmulator::from_elf(&elf_file, &[], &[]);
+//! emulator.executor.profiler = Some(Profiler::default());
+//! let _ = emulator.execute(false);
+//!
+//! let profiler = emulator.executor.profiler.as_ref().unwrap();
+//! let (opcode, cycles) = &profiler.report()[0];
+//! println!("{opcode}: {cycles} cycles ({:.1}%)", profiler.fraction(opcode) * 100.0);
+//! ```
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+};
+
+use crate::{
+    error::{Result, VMError},
+    riscv::Opcode,
+};
+
+#[derive(Debug, Default, Clone)]
+pub struct Profiler {
+    // Number of executed instructions per opcode
+    opcodes: HashMap<Opcode, u64>,
+    // Total number of executed instructions
+    total_cycles: u64,
+    // Currently open regions as (label, path, total cycle count at the start)
+    open_regions: Vec<(String, String, u64)>,
+    // Cycles spent in closed regions, keyed by the path of the region
+    regions: BTreeMap<String, u64>,
+}
+
+impl Profiler {
+    /// Records a single execution of `opcode`.
+    pub fn record(&mut self, opcode: &Opcode) {
+        *self.opcodes.entry(opcode.clone()).or_default() += 1;
+        self.total_cycles += 1;
+    }
+
+    /// Opens a region nested in the currently open one.
+    pub fn enter_region(&mut self, label: &str) {
+        let path = match self.open_regions.last() {
+            Some((_, parent, _)) => format!("{parent}/{label}"),
+            None => label.to_string(),
+        };
+        self.open_regions
+            .push((label.to_string(), path, self.total_cycles));
+    }
+
+    /// Closes the innermost region, which must be labelled with `label`.
+    ///
+    /// Returns [`VMError::InvalidProfileLabel`] if regions are not properly nested.
+    pub fn exit_region(&mut self, label: &str) -> Result<()> {
+        if self
+            .open_regions
+            .last()
+            .is_none_or(|(open_label, _, _)| open_label != label)
+        {
+            return Err(VMError::InvalidProfileLabel(format!("$#{label}")));
+        }
+
+        let (_, path, start) = self.open_regions.pop().expect("region is open");
+        *self.regions.entry(path).or_default() += self.total_cycles - start;
+        Ok(())
+    }
+
+    pub fn total_cycles(&self) -> u64 {
+        self.total_cycles
+    }
+
+    /// Returns the cycle count of every executed opcode, sorted by cycle count in descending order.
+    pub fn report(&self) -> Vec<(Opcode, u64)> {
+        let mut report: Vec<(Opcode, u64)> = self
+            .opcodes
+            .iter()
+            .map(|(opcode, &cycles)| (opcode.clone(), cycles))
+            .collect();
+        report.sort_by(|(a, a_cycles), (b, b_cycles)| {
+            b_cycles.cmp(a_cycles).then_with(|| a.name().cmp(b.name()))
+        });
+        report
+    }
+
+    /// Returns the fraction of total cycles consumed by `opcode`.
+    pub fn fraction(&self, opcode: &Opcode) -> f64 {
+        if self.total_cycles == 0 {
+            return 0.0;
+        }
+        self.opcodes.get(opcode).copied().unwrap_or_default() as f64 / self.total_cycles as f64
+    }
+
+    /// Returns the cycles spent in every closed region, keyed by the path of the region.
+    pub fn regions(&self) -> &BTreeMap<String, u64> {
+        &self.regions
+    }
+}
+
+impl fmt::Display for Profiler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:<12} {:>12} {:>8}", "Opcode", "Cycles", "Share")?;
+        for (opcode, cycles) in self.report() {
+            writeln!(
+                f,
+                "{:<12} {:>12} {:>7.2}%",
+                opcode.to_string(),
+                cycles,
+                self.fraction(&opcode) * 100.0
+            )?;
+        }
+        writeln!(f, "{:<12} {:>12}", "Total", self.total_cycles)?;
+
+        if !self.regions.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "{:<24} {:>12}", "Region", "Cycles")?;
+            for (path, cycles) in &self.regions {
+                writeln!(f, "{path:<24} {cycles:>12}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::riscv::BuiltinOpcode;
+
+    #[test]
+    fn test_profiler_regions() {
+        let add = Opcode::from(BuiltinOpcode::ADD);
+        let lw = Opcode::from(BuiltinOpcode::LW);
+
+        let mut profiler = Profiler::default();
+        profiler.enter_region("main");
+        profiler.record(&add);
+        profiler.enter_region("fib");
+        profiler.record(&lw);
+        profiler.record(&lw);
+        assert_eq!(
+            profiler.exit_region("main"),
+            Err(VMError::InvalidProfileLabel("$#main".into()))
+        );
+        profiler.exit_region("fib").unwrap();
+        profiler.record(&add);
+        profiler.exit_region("main").unwrap();
+        profiler.record(&lw);
+
+        assert_eq!(profiler.total_cycles(), 5);
+        assert_eq!(profiler.report(), vec![(lw.clone(), 3), (add.clone(), 2)]);
+        assert_eq!(profiler.fraction(&add), 0.4);
+        assert_eq!(
+            profiler.regions().clone(),
+            BTreeMap::from([("main".into(), 4), ("main/fib".into(), 2)])
+        );
+        assert_eq!(
+            profiler.exit_region("main"),
+            Err(VMError::InvalidProfileLabel("$#main".into()))
+        );
+    }
+}
@@ -187,7 +187,9 @@ impl Default for InstructionExecutorRegistry {
                 )), // jalr
                 None, // ecall, handled by src/system/syscall.rs instead
                 None, // ebreak
-                None, // fence
+                Some(register_instruction_executor!(
+                    instructions::FenceInstruction::evaluator
+                )), // fence
                 Some(register_instruction_executor!(
                     instructions::SbInstruction::evaluator
                 )), // sb
@@ -248,12 +250,22 @@ impl InstructionExecutorRegistry {
             // Safety: the length of `builtins` is statically guaranteed to be equal to the number
             // of variants in `BuiltinOpcode`.
             #[allow(clippy::unnecessary_lazy_evaluations)]
-            self.builtins[idx].ok_or_else(|| VMError::UnimplementedInstruction(op.clone()))
+            self.builtins[idx].ok_or_else(|| VMError::UnimplementedInstruction {
+                opcode: op.clone(),
+                pc: None,
+            })
         } else {
             if let Some(func) = self.precompiles.get(op) {
                 return Ok(*func);
             }
 
+            #[cfg(feature = "emulate-atomics")]
+            if op.raw() == instructions::AMO_OPCODE {
+                return Ok(register_instruction_executor!(
+                    instructions::AmoInstruction::evaluator
+                ));
+            }
+
             Err(VMError::UndefinedInstruction(op.clone()))
         }
     }
@@ -0,0 +1,75 @@
+//! Checkpoints of the Harvard emulator state.
+//!
+//! A `VmSnapshot` captures the registers, program counter, memory contents, and remaining private
+//! input at an arbitrary point of the execution. Restoring it with [`HarvardEmulator::restore`]
+//! resets the emulator to that point, so that only the segment of the program following the
+//! checkpoint has to be re-executed.
+//!
+//! With the `snapshot-serde` feature enabled `VmSnapshot` implements `serde::Serialize` and
+//! `serde::Deserialize`, allowing checkpoints to be stored outside of the process.
+//!
+//! # Usage
+//!
+//! ```rust
+//! use nexus_vm::elf::ElfFile;
+//! use nexus_vm::emulator::{Emulator, HarvardEmulator};
+//! use nexus_vm::error::VMError;
+//!
+//! let elf_file = ElfFile::from_path("test/fib_10.elf").unwrap();
+//! let mut emulator = HarvardEmulator::from_elf(&elf_file, &[], &[]);
+//! let snapshot = emulator.snapshot();
+//!
+//! assert_eq!(emulator.execute(false), Err(VMError::VMExited(0)));
+//!
+//! // Replay the program from the checkpoint.
+//! emulator.restore(snapshot).unwrap();
+//! assert_eq!(emulator.execute(false), Err(VMError::VMExited(0)));
+//! ```
+//!
+//! [`HarvardEmulator::restore`]: super::HarvardEmulator::restore
+use std::collections::{HashMap, VecDeque};
+
+use super::{memory_stats::MemoryStats, MemoryLayout};
+use crate::{
+    cpu::RegisterFile,
+    memory::{UnifiedMemory, VariableMemory, WO},
+};
+
+/// State of a [`HarvardEmulator`](super::HarvardEmulator) at a specific execution step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "snapshot-serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct VmSnapshot {
+    pub(crate) registers: RegisterFile,
+    pub(crate) pc: u32,
+    pub(crate) cycles: u64,
+    pub(crate) global_clock: usize,
+    pub(crate) access_timestamps: HashMap<u32, usize>,
+    pub(crate) private_input_tape: VecDeque<u8>,
+    pub(crate) data_memory: UnifiedMemory,
+    pub(crate) output_memory: VariableMemory<WO>,
+    pub(crate) memory_stats: MemoryStats,
+    pub(crate) memory_layout: Option<MemoryLayout>,
+}
+
+impl VmSnapshot {
+    /// Returns the program counter of the next instruction to be executed.
+    pub fn pc(&self) -> u32 {
+        self.pc
+    }
+
+    pub fn registers(&self) -> &RegisterFile {
+        &self.registers
+    }
+
+    pub fn global_clock(&self) -> usize {
+        self.global_clock
+    }
+
+    /// Returns the custom memory layout of the emulator, if any.
+    pub fn memory_layout(&self) -> Option<&MemoryLayout> {
+        self.memory_layout.as_ref()
+    }
+}
@@ -154,6 +154,9 @@ pub struct BasicBlockEntry {
     pub start: u32,
     pub end: u32,
     pub block: BasicBlock,
+    // Byte offset of every instruction from `start`, compressed instructions are two bytes long
+    #[cfg(feature = "riscv-c")]
+    offsets: Vec<u32>,
 }
 
 impl BasicBlockEntry {
@@ -161,9 +164,57 @@ impl BasicBlockEntry {
         BasicBlockEntry {
             start,
             end: start + (block.len() * WORD_SIZE) as u32,
+            #[cfg(feature = "riscv-c")]
+            offsets: (0..block.len() as u32)
+                .map(|i| i * WORD_SIZE as u32)
+                .collect(),
             block,
         }
     }
+
+    /// Creates an entry for a block whose instructions have the given sizes in bytes.
+    #[cfg(feature = "riscv-c")]
+    pub fn with_sizes(start: u32, block: BasicBlock, sizes: &[u32]) -> Self {
+        assert_eq!(block.len(), sizes.len());
+        let offsets: Vec<u32> = sizes
+            .iter()
+            .scan(0, |offset, size| {
+                let current = *offset;
+                *offset += size;
+                Some(current)
+            })
+            .collect();
+        BasicBlockEntry {
+            start,
+            end: start + sizes.iter().sum::<u32>(),
+            block,
+            offsets,
+        }
+    }
+
+    /// Returns the index of the instruction at `pc` in the block.
+    #[cfg(not(feature = "riscv-c"))]
+    pub fn index_of(&self, pc: u32) -> usize {
+        (pc - self.start) as usize / WORD_SIZE
+    }
+
+    /// Returns the index of the instruction at `pc` in the block.
+    #[cfg(feature = "riscv-c")]
+    pub fn index_of(&self, pc: u32) -> usize {
+        self.offsets
+            .binary_search(&(pc - self.start))
+            .expect("pc must point to the start of an instruction")
+    }
+
+    /// Returns the size in bytes of the instruction at `index`.
+    #[cfg(feature = "riscv-c")]
+    pub fn instruction_size(&self, index: usize) -> u32 {
+        let next = self
+            .offsets
+            .get(index + 1)
+            .map_or(self.end, |offset| self.start + offset);
+        next - (self.start + self.offsets[index])
+    }
 }
 
 pub trait InternalView {
@@ -1,6 +1,8 @@
 pub use nexus_common::error::*;
 
 use nexus_common::riscv::Opcode;
+
+use crate::emulator::MemoryLayout;
 use thiserror::Error;
 
 /// Errors related to VM operations.
@@ -43,21 +45,73 @@ pub enum VMError {
     #[error("Duplicate Opcode/Instruction in registry")]
     DuplicateInstruction(Opcode),
 
+    // Duplicate syscall code in the syscall table.
+    #[error("Duplicate syscall in registry: code=0x{0:X}")]
+    DuplicateSyscall(u32),
+
     // Undefined instruction
     #[error("Undefined instruction \"{0}\"")]
     UndefinedInstruction(Opcode),
 
-    // Unimplemented instruction (with a valid opcode)
-    #[error("Unimplemented instruction \"{0}\"")]
-    UnimplementedInstruction(Opcode),
-
-    // Unimplemented instruction (with a valid opcode) found at a specific PC
-    #[error("Unimplemented instruction \"{0}\" at pc=0x{1:08X}")]
-    UnimplementedInstructionAt(Opcode, u32),
+    // Unimplemented instruction (with a valid opcode), `pc` is set once the instruction is executed
+    #[error("Unimplemented instruction \"{opcode}\"{}", at_pc(*.pc))]
+    UnimplementedInstruction { opcode: Opcode, pc: Option<u32> },
 
     // Unsupported instruction (i.e., one with an invalid opcode)
     #[error("Unsupported instruction \"{0}\"")]
     UnsupportedInstruction(Opcode),
+
+    // Division by zero, only raised when trapping is enabled; `opcode` is the encoded instruction.
+    #[error("Division by zero ({}) at pc=0x{pc:08X}", division_kind(*.opcode))]
+    DivisionByZero { pc: u32, opcode: u32 },
+
+    // Halfword or word memory access at an address that isn't a multiple of the access size.
+    #[error("Misaligned memory access to 0x{addr:08X}, requires {required_alignment}-byte alignment, pc=0x{pc:08X}")]
+    InvalidAlignment {
+        addr: u32,
+        required_alignment: u32,
+        pc: u32,
+    },
+
+    // Atomic instruction executed without the `emulate-atomics` feature; `opcode` is the encoded instruction.
+    #[error("Atomic instruction 0x{opcode:08X} at pc=0x{pc:08X} is not supported, the zkVM executes a single thread")]
+    AtomicOperationInSingleThreadedContext { opcode: u32, pc: u32 },
+
+    // Execution exceeded the configured wall-clock limit.
+    #[error("VM execution timed out after {step_count} steps ({elapsed_ns} ns)")]
+    Timeout { elapsed_ns: u64, step_count: u64 },
+
+    // The same sequence of instructions was executed too many times in a row, see `LoopDetector`.
+    #[error("Suspected infinite loop through pcs {cycle_pcs:08X?}")]
+    SuspectedInfiniteLoop { cycle_pcs: Vec<u32> },
+
+    // ELF file could not be parsed
+    #[error("Invalid ELF file: {0}")]
+    InvalidElf(String),
+
+    // Snapshot restored into an emulator with a different memory layout.
+    #[error(
+        "Snapshot memory layout {snapshot:?} does not match emulator memory layout {emulator:?}"
+    )]
+    SnapshotLayoutMismatch {
+        snapshot: Option<MemoryLayout>,
+        emulator: Option<MemoryLayout>,
+    },
+}
+
+/// Returns whether the encoded DIV/DIVU/REM/REMU instruction is signed, based on the lowest bit of funct3.
+fn division_kind(opcode: u32) -> &'static str {
+    if (opcode >> 12) & 1 == 0 {
+        "signed"
+    } else {
+        "unsigned"
+    }
+}
+
+/// Formats the optional pc of an error, empty if it is unknown.
+fn at_pc(pc: Option<u32>) -> String {
+    pc.map(|pc| format!(" at pc=0x{pc:08X}"))
+        .unwrap_or_default()
 }
 
 /// Result type for VM functions that can produce errors.
@@ -0,0 +1,143 @@
+//! Ordered log of the memory accesses performed during execution.
+//!
+//! Memory checking needs the full sequence of reads and writes to every address, in the order they
+//! happened. The emulator appends to the log as instructions execute, once recording is enabled
+//! with [`Executor::log_memory_accesses`](crate::emulator::Executor::log_memory_accesses).
+//!
+//! The log is not part of the trace handed to the prover, whose memory checking still uses the
+//! per-step memory records.
+use serde::{Deserialize, Serialize};
+
+use super::{MemAccessSize, MemoryRecord, MemoryRecords};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ReadOrWrite {
+    Read,
+    Write,
+}
+
+/// A single memory access.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MemoryAccess {
+    /// Program counter of the instruction performing the access.
+    pub pc: u32,
+    pub addr: u32,
+    /// Value read or written, zero-extended to 32 bits.
+    pub value: u32,
+    pub size: MemAccessSize,
+    pub kind: ReadOrWrite,
+    /// Global clock of the emulator at the time of the access.
+    pub timestamp: u64,
+}
+
+/// Memory accesses ordered by execution, accesses of a single instruction are ordered by address
+/// with reads before writes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryAccessLog {
+    accesses: Vec<MemoryAccess>,
+}
+
+impl MemoryAccessLog {
+    /// Appends the memory records of the instruction at `pc`.
+    pub fn record(&mut self, pc: u32, records: &MemoryRecords) {
+        let start = self.accesses.len();
+        self.accesses.extend(records.iter().map(|record| {
+            let (kind, size, addr, value, timestamp) = match *record {
+                MemoryRecord::LoadRecord((size, addr, value), timestamp) => {
+                    (ReadOrWrite::Read, size, addr, value, timestamp)
+                }
+                MemoryRecord::StoreRecord((size, addr, value, _), timestamp) => {
+                    (ReadOrWrite::Write, size, addr, value, timestamp)
+                }
+            };
+            MemoryAccess {
+                pc,
+                addr,
+                value,
+                size,
+                kind,
+                timestamp: timestamp as u64,
+            }
+        }));
+        // Records are stored in a hash set, sort them for deterministic output.
+        self.accesses[start..]
+            .sort_by_key(|access| (access.kind == ReadOrWrite::Write, access.addr));
+    }
+
+    /// Drops the accesses performed at or after `timestamp`.
+    pub(crate) fn truncate(&mut self, timestamp: u64) {
+        let len = self
+            .accesses
+            .partition_point(|access| access.timestamp < timestamp);
+        self.accesses.truncate(len);
+    }
+
+    pub fn len(&self) -> usize {
+        self.accesses.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.accesses.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &MemoryAccess> {
+        self.accesses.iter()
+    }
+
+    /// Returns the accesses ordered by timestamp, which is the execution order.
+    pub fn sorted_by_timestamp(&self) -> Vec<&MemoryAccess> {
+        let mut accesses: Vec<&MemoryAccess> = self.accesses.iter().collect();
+        accesses.sort_by_key(|access| access.timestamp);
+        accesses
+    }
+
+    /// Returns the accesses grouped by address, accesses to the same address are kept in execution order.
+    pub fn sorted_by_address(&self) -> Vec<&MemoryAccess> {
+        let mut accesses: Vec<&MemoryAccess> = self.accesses.iter().collect();
+        accesses.sort_by_key(|access| access.addr);
+        accesses
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_access_log() {
+        let mut log = MemoryAccessLog::default();
+        log.record(
+            0x1000,
+            &MemoryRecords::from([
+                MemoryRecord::StoreRecord((MemAccessSize::Word, 0x200, 7, 0), 1),
+                MemoryRecord::LoadRecord((MemAccessSize::Word, 0x300, 5), 1),
+            ]),
+        );
+        log.record(
+            0x1004,
+            &MemoryRecords::from([MemoryRecord::LoadRecord((MemAccessSize::Byte, 0x200, 7), 2)]),
+        );
+        assert_eq!(log.len(), 3);
+
+        let by_timestamp: Vec<_> = log
+            .sorted_by_timestamp()
+            .iter()
+            .map(|access| (access.pc, access.addr, access.kind))
+            .collect();
+        assert_eq!(
+            by_timestamp,
+            [
+                (0x1000, 0x300, ReadOrWrite::Read),
+                (0x1000, 0x200, ReadOrWrite::Write),
+                (0x1004, 0x200, ReadOrWrite::Read),
+            ]
+        );
+
+        let by_address: Vec<_> = log
+            .sorted_by_address()
+            .iter()
+            .map(|access| (access.addr, access.timestamp, access.value))
+            .collect();
+        assert_eq!(by_address, [(0x200, 1, 7), (0x200, 2, 7), (0x300, 1, 5)]);
+    }
+}
@@ -52,6 +52,11 @@ use nexus_common::{bytes_to_words, word_align, words_to_bytes};
 use super::{LoadOp, MemAccessSize, MemoryProcessor, Mode, StoreOp, NA, RO, RW, WO};
 
 #[derive(Default, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "snapshot-serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
 pub struct FixedMemory<M: Mode> {
     pub base_address: u32,
     pub max_len: usize,
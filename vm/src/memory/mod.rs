@@ -1,3 +1,4 @@
+mod access_log;
 mod fixed;
 mod unified;
 mod variable;
@@ -7,6 +8,7 @@ pub use nexus_common::memory::traits::{
     StoreOps, NA, RO, RW, WO,
 };
 
+pub use access_log::{MemoryAccess, MemoryAccessLog, ReadOrWrite};
 pub use fixed::FixedMemory;
 pub use unified::{Modes, UnifiedMemory};
 pub use variable::VariableMemory;
@@ -84,6 +84,10 @@ use super::{
 };
 
 #[derive(Debug, Clone, Eq, PartialEq, FromPrimitive)]
+#[cfg_attr(
+    feature = "snapshot-serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
 pub enum Modes {
     NA = 0,
     RO = 1,
@@ -93,6 +97,10 @@ pub enum Modes {
 
 // nb: we store outside the map becaues `rangemap::RangeMap` does not support a `get_mut` interface (https://github.com/jeffparsons/rangemap/issues/85)
 #[derive(Default, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "snapshot-serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
 pub struct UnifiedMemory {
     // lookup for correct fixed memory, if any
     meta: RangeMap<u32, Modes>,
@@ -61,6 +61,11 @@ use nexus_common::words_to_bytes;
 use super::{LoadOp, MemAccessSize, MemoryProcessor, Mode, StoreOp, RO, RW, WO};
 
 #[derive(Default, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "snapshot-serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound = "")
+)]
 pub struct VariableMemory<M: Mode>(BTreeMap<u32, u32>, PhantomData<M>);
 
 impl<M: Mode> From<BTreeMap<u32, u32>> for VariableMemory<M> {
@@ -0,0 +1,432 @@
+//! # Decoding of RISC-V compressed (C extension) instructions
+//!
+//! Compressed instructions are 16-bit encodings of the most common RV32I instructions. Every compressed
+//! instruction has a 32-bit equivalent, so they are decoded by expanding them first and then handing the
+//! 32-bit encoding over to [`decode_instruction`].
+//!
+//! Floating-point loads and stores, as well as RV64-only encodings, are not supported and decode into
+//! `unimpl`, the same as unrecognized 32-bit instructions.
+//!
+//! Only available with the `riscv-c` feature. With it, the emulators fetch instructions as 16-bit parcels, so
+//! that compressed and 32-bit instructions can be mixed, and advance the PC by two bytes past compressed ones.
+//!
+//! ## Usage Example
+//!
+//! ```rust
+//! use nexus_vm::riscv::{decode_compressed_instruction, expand_compressed, is_compressed};
+//!
+//! // c.addi a0, -3
+//! assert!(is_compressed(0x1575));
+//! assert_eq!(expand_compressed(0x1575), Some(0xffd50513));
+//! println!("{}", decode_compressed_instruction(0x1575));
+//! ```
+
+use super::{decode_instruction, BasicBlock, Instruction};
+
+const OPCODE_LOAD: u32 = 0b0000011;
+const OPCODE_OP_IMM: u32 = 0b0010011;
+const OPCODE_STORE: u32 = 0b0100011;
+const OPCODE_OP: u32 = 0b0110011;
+const OPCODE_LUI: u32 = 0b0110111;
+const OPCODE_BRANCH: u32 = 0b1100011;
+const OPCODE_JALR: u32 = 0b1100111;
+const OPCODE_JAL: u32 = 0b1101111;
+
+const EBREAK: u32 = 0x00100073;
+
+const REG_ZERO: u32 = 0;
+const REG_RA: u32 = 1;
+const REG_SP: u32 = 2;
+
+/// Returns `true` if the lowest two bits of `instruction` mark a 16-bit encoding.
+pub fn is_compressed(instruction: u16) -> bool {
+    instruction & 0b11 != 0b11
+}
+
+/// Expands a compressed instruction into its 32-bit equivalent.
+///
+/// Returns `None` for illegal, reserved, and unsupported encodings.
+pub fn expand_compressed(instruction: u16) -> Option<u32> {
+    let inst = instruction as u32;
+    let funct3 = bits(inst, 15, 13);
+    let rd = bits(inst, 11, 7);
+    let rs2 = bits(inst, 6, 2);
+    // Registers x8-x15 of the CIW, CL, CS, CA, and CB formats, rd'/rs2' and rs1'/rd' respectively.
+    let rd_prime = bits(inst, 4, 2) + 8;
+    let rs1_prime = bits(inst, 9, 7) + 8;
+
+    match (inst & 0b11, funct3) {
+        // The all-zero instruction is illegal.
+        _ if inst == 0 => None,
+        // C.ADDI4SPN (CIW): addi rd', x2, nzuimm
+        (0b00, 0b000) => {
+            let imm = (bits(inst, 12, 11) << 4)
+                | (bits(inst, 10, 7) << 6)
+                | (bits(inst, 6, 6) << 2)
+                | (bits(inst, 5, 5) << 3);
+            (imm != 0).then(|| i_type(OPCODE_OP_IMM, rd_prime, 0b000, REG_SP, imm))
+        }
+        // C.LW (CL): lw rd', uimm(rs1')
+        (0b00, 0b010) => Some(i_type(
+            OPCODE_LOAD,
+            rd_prime,
+            0b010,
+            rs1_prime,
+            cl_offset(inst),
+        )),
+        // C.SW (CS): sw rs2', uimm(rs1')
+        (0b00, 0b110) => Some(s_type(
+            OPCODE_STORE,
+            0b010,
+            rs1_prime,
+            rd_prime,
+            cl_offset(inst),
+        )),
+        // C.NOP, C.ADDI (CI): addi rd, rd, imm
+        (0b01, 0b000) => Some(i_type(OPCODE_OP_IMM, rd, 0b000, rd, ci_imm(inst))),
+        // C.JAL (CJ): jal x1, offset
+        (0b01, 0b001) => Some(j_type(REG_RA, cj_offset(inst))),
+        // C.LI (CI): addi rd, x0, imm
+        (0b01, 0b010) => Some(i_type(OPCODE_OP_IMM, rd, 0b000, REG_ZERO, ci_imm(inst))),
+        // C.ADDI16SP (CI): addi x2, x2, nzimm
+        (0b01, 0b011) if rd == REG_SP => {
+            let imm = sign_extend(
+                (bits(inst, 12, 12) << 9)
+                    | (bits(inst, 6, 6) << 4)
+                    | (bits(inst, 5, 5) << 6)
+                    | (bits(inst, 4, 3) << 7)
+                    | (bits(inst, 2, 2) << 5),
+                10,
+            );
+            (imm != 0).then(|| i_type(OPCODE_OP_IMM, REG_SP, 0b000, REG_SP, imm))
+        }
+        // C.LUI (CI): lui rd, nzimm
+        (0b01, 0b011) => {
+            let imm = sign_extend((bits(inst, 12, 12) << 17) | (bits(inst, 6, 2) << 12), 18);
+            (imm != 0).then_some((imm & 0xfffff000) | (rd << 7) | OPCODE_LUI)
+        }
+        (0b01, 0b100) => match (bits(inst, 11, 10), bits(inst, 12, 12)) {
+            // RV32 shift amounts are at most 31.
+            (0b00 | 0b01, 1) => None,
+            // C.SRLI (CB): srli rd', rd', shamt
+            (0b00, _) => Some(i_type(OPCODE_OP_IMM, rs1_prime, 0b101, rs1_prime, rs2)),
+            // C.SRAI (CB): srai rd', rd', shamt
+            (0b01, _) => Some(i_type(
+                OPCODE_OP_IMM,
+                rs1_prime,
+                0b101,
+                rs1_prime,
+                (0b0100000 << 5) | rs2,
+            )),
+            // C.ANDI (CB): andi rd', rd', imm
+            (0b10, _) => Some(i_type(
+                OPCODE_OP_IMM,
+                rs1_prime,
+                0b111,
+                rs1_prime,
+                ci_imm(inst),
+            )),
+            // C.SUB, C.XOR, C.OR, C.AND (CA): op rd', rd', rs2'
+            (0b11, 0) => {
+                let (funct7, funct3) = match bits(inst, 6, 5) {
+                    0b00 => (0b0100000, 0b000),
+                    0b01 => (0b0000000, 0b100),
+                    0b10 => (0b0000000, 0b110),
+                    _ => (0b0000000, 0b111),
+                };
+                Some(r_type(funct7, rs1_prime, funct3, rs1_prime, rd_prime))
+            }
+            // C.SUBW and C.ADDW are RV64-only.
+            _ => None,
+        },
+        // C.J (CJ): jal x0, offset
+        (0b01, 0b101) => Some(j_type(REG_ZERO, cj_offset(inst))),
+        // C.BEQZ, C.BNEZ (CB): beq/bne rs1', x0, offset
+        (0b01, 0b110 | 0b111) => {
+            let offset = sign_extend(
+                (bits(inst, 12, 12) << 8)
+                    | (bits(inst, 11, 10) << 3)
+                    | (bits(inst, 6, 5) << 6)
+                    | (bits(inst, 4, 3) << 1)
+                    | (bits(inst, 2, 2) << 5),
+                9,
+            );
+            Some(b_type(funct3 & 0b001, rs1_prime, REG_ZERO, offset))
+        }
+        // C.SLLI (CI): slli rd, rd, shamt
+        (0b10, 0b000) => {
+            (bits(inst, 12, 12) == 0).then(|| i_type(OPCODE_OP_IMM, rd, 0b001, rd, rs2))
+        }
+        // C.LWSP (CI): lw rd, uimm(x2)
+        (0b10, 0b010) => {
+            let offset =
+                (bits(inst, 12, 12) << 5) | (bits(inst, 6, 4) << 2) | (bits(inst, 3, 2) << 6);
+            (rd != REG_ZERO).then(|| i_type(OPCODE_LOAD, rd, 0b010, REG_SP, offset))
+        }
+        (0b10, 0b100) => match (bits(inst, 12, 12), rd, rs2) {
+            // C.JR requires a non-zero rs1.
+            (0, REG_ZERO, REG_ZERO) => None,
+            // C.JR (CR): jalr x0, 0(rs1)
+            (0, rs1, REG_ZERO) => Some(i_type(OPCODE_JALR, REG_ZERO, 0b000, rs1, 0)),
+            // C.MV (CR): add rd, x0, rs2
+            (0, rd, rs2) => Some(r_type(0, rd, 0b000, REG_ZERO, rs2)),
+            // C.EBREAK (CR)
+            (1, REG_ZERO, REG_ZERO) => Some(EBREAK),
+            // C.JALR (CR): jalr x1, 0(rs1)
+            (1, rs1, REG_ZERO) => Some(i_type(OPCODE_JALR, REG_RA, 0b000, rs1, 0)),
+            // C.ADD (CR): add rd, rd, rs2
+            (_, rd, rs2) => Some(r_type(0, rd, 0b000, rd, rs2)),
+        },
+        // C.SWSP (CSS): sw rs2, uimm(x2)
+        (0b10, 0b110) => {
+            let offset = (bits(inst, 12, 9) << 2) | (bits(inst, 8, 7) << 6);
+            Some(s_type(OPCODE_STORE, 0b010, REG_SP, rs2, offset))
+        }
+        // Floating-point loads and stores, and 32-bit encodings.
+        _ => None,
+    }
+}
+
+/// Decodes a compressed instruction, unsupported encodings are decoded as `unimpl`.
+pub fn decode_compressed_instruction(instruction: u16) -> Instruction {
+    expand_compressed(instruction)
+        .map(decode_instruction)
+        .unwrap_or_else(Instruction::unimpl)
+}
+
+/// Decodes a stream of 16-bit parcels until the end of a basic block, compressed and 32-bit instructions
+/// may be mixed freely.
+///
+/// Returns the block together with the size in bytes of each of its instructions. A trailing 32-bit
+/// instruction without its upper parcel is dropped.
+pub fn decode_parcels_until_end_of_a_block(
+    parcels: impl IntoIterator<Item = u16>,
+) -> (BasicBlock, Vec<u32>) {
+    let mut block = BasicBlock::default();
+    let mut sizes = Vec::new();
+
+    let mut parcels = parcels.into_iter();
+    while let Some(low) = parcels.next() {
+        let (decoded_instruction, size) = if is_compressed(low) {
+            (decode_compressed_instruction(low), 2)
+        } else if let Some(high) = parcels.next() {
+            (decode_instruction(low as u32 | ((high as u32) << 16)), 4)
+        } else {
+            break;
+        };
+
+        let pc_changed = decoded_instruction.is_branch_or_jump_instruction();
+
+        block.0.push(decoded_instruction);
+        sizes.push(size);
+
+        if pc_changed {
+            break;
+        }
+    }
+
+    (block, sizes)
+}
+
+/// Returns bits `hi..=lo` of `inst`, shifted down to bit zero.
+#[inline(always)]
+fn bits(inst: u32, hi: u32, lo: u32) -> u32 {
+    (inst >> lo) & ((1 << (hi - lo + 1)) - 1)
+}
+
+/// Sign-extends the lowest `width` bits of `value`.
+#[inline(always)]
+fn sign_extend(value: u32, width: u32) -> u32 {
+    let shift = 32 - width;
+    (((value << shift) as i32) >> shift) as u32
+}
+
+/// Returns the sign-extended 6-bit immediate of the CI format.
+fn ci_imm(inst: u32) -> u32 {
+    sign_extend((bits(inst, 12, 12) << 5) | bits(inst, 6, 2), 6)
+}
+
+/// Returns the word-aligned offset of the CL and CS formats.
+fn cl_offset(inst: u32) -> u32 {
+    (bits(inst, 12, 10) << 3) | (bits(inst, 6, 6) << 2) | (bits(inst, 5, 5) << 6)
+}
+
+/// Returns the sign-extended jump offset of the CJ format.
+fn cj_offset(inst: u32) -> u32 {
+    sign_extend(
+        (bits(inst, 12, 12) << 11)
+            | (bits(inst, 11, 11) << 4)
+            | (bits(inst, 10, 9) << 8)
+            | (bits(inst, 8, 8) << 10)
+            | (bits(inst, 7, 7) << 6)
+            | (bits(inst, 6, 6) << 7)
+            | (bits(inst, 5, 3) << 1)
+            | (bits(inst, 2, 2) << 5),
+        12,
+    )
+}
+
+fn r_type(funct7: u32, rd: u32, funct3: u32, rs1: u32, rs2: u32) -> u32 {
+    (funct7 << 25) | (rs2 << 20) | (rs1 << 15) | (funct3 << 12) | (rd << 7) | OPCODE_OP
+}
+
+fn i_type(opcode: u32, rd: u32, funct3: u32, rs1: u32, imm: u32) -> u32 {
+    ((imm & 0xfff) << 20) | (rs1 << 15) | (funct3 << 12) | (rd << 7) | opcode
+}
+
+fn s_type(opcode: u32, funct3: u32, rs1: u32, rs2: u32, imm: u32) -> u32 {
+    (bits(imm, 11, 5) << 25)
+        | (rs2 << 20)
+        | (rs1 << 15)
+        | (funct3 << 12)
+        | (bits(imm, 4, 0) << 7)
+        | opcode
+}
+
+fn b_type(funct3: u32, rs1: u32, rs2: u32, imm: u32) -> u32 {
+    (bits(imm, 12, 12) << 31)
+        | (bits(imm, 10, 5) << 25)
+        | (rs2 << 20)
+        | (rs1 << 15)
+        | (funct3 << 12)
+        | (bits(imm, 4, 1) << 8)
+        | (bits(imm, 11, 11) << 7)
+        | OPCODE_BRANCH
+}
+
+fn j_type(rd: u32, imm: u32) -> u32 {
+    (bits(imm, 20, 20) << 31)
+        | (bits(imm, 10, 1) << 21)
+        | (bits(imm, 11, 11) << 20)
+        | (bits(imm, 19, 12) << 12)
+        | (rd << 7)
+        | OPCODE_JAL
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_expands(cases: &[(&str, u16, u32)]) {
+        for &(asm, compressed, expanded) in cases {
+            assert!(is_compressed(compressed), "{asm}");
+            assert_eq!(
+                expand_compressed(compressed),
+                Some(expanded),
+                "{asm}: expected {expanded:#010x}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_expand_cr_format() {
+        assert_expands(&[
+            ("c.jr ra", 0x8082, 0x00008067),
+            ("c.mv a0, t1", 0x851a, 0x00600533),
+            ("c.ebreak", 0x9002, 0x00100073),
+            ("c.jalr t2", 0x9382, 0x000380e7),
+            ("c.add s2, s3", 0x994e, 0x01390933),
+        ]);
+    }
+
+    #[test]
+    fn test_expand_ci_format() {
+        assert_expands(&[
+            ("c.addi a0, -3", 0x1575, 0xffd50513),
+            ("c.nop", 0x0001, 0x00000013),
+            ("c.li a5, 17", 0x47c5, 0x01100793),
+            ("c.addi16sp sp, -64", 0x7139, 0xfc010113),
+            ("c.lui a4, 0xfffe1", 0x7705, 0xfffe1737),
+            ("c.slli t0, 12", 0x02b2, 0x00c29293),
+            ("c.lwsp ra, 12(sp)", 0x40b2, 0x00c12083),
+        ]);
+    }
+
+    #[test]
+    fn test_expand_css_format() {
+        assert_expands(&[("c.swsp s4, 252(sp)", 0xdfd2, 0x0f412e23)]);
+    }
+
+    #[test]
+    fn test_expand_ciw_format() {
+        assert_expands(&[("c.addi4spn s0, sp, 16", 0x0800, 0x01010413)]);
+    }
+
+    #[test]
+    fn test_expand_cl_format() {
+        assert_expands(&[("c.lw a0, 4(a1)", 0x41c8, 0x0045a503)]);
+    }
+
+    #[test]
+    fn test_expand_cs_format() {
+        assert_expands(&[("c.sw a2, 8(a3)", 0xc690, 0x00c6a423)]);
+    }
+
+    #[test]
+    fn test_expand_ca_format() {
+        assert_expands(&[
+            ("c.sub s0, a5", 0x8c1d, 0x40f40433),
+            ("c.xor a0, a1", 0x8d2d, 0x00b54533),
+            ("c.or a2, a3", 0x8e55, 0x00d66633),
+            ("c.and a4, a5", 0x8f7d, 0x00f77733),
+        ]);
+    }
+
+    #[test]
+    fn test_expand_cb_format() {
+        assert_expands(&[
+            ("c.srli s1, 3", 0x808d, 0x0034d493),
+            ("c.srai a5, 31", 0x87fd, 0x41f7d793),
+            ("c.andi a1, -8", 0x99e1, 0xff85f593),
+            ("c.beqz a0, -256", 0xd101, 0xf00500e3),
+            ("c.bnez s1, 30", 0xec99, 0x00049f63),
+        ]);
+    }
+
+    #[test]
+    fn test_expand_cj_format() {
+        assert_expands(&[
+            ("c.jal -38", 0x3fe9, 0xfdbff0ef),
+            ("c.j 2044", 0xaff5, 0x7fc0006f),
+        ]);
+    }
+
+    #[test]
+    fn test_unsupported_encodings() {
+        // Illegal all-zero instruction.
+        assert_eq!(expand_compressed(0x0000), None);
+        // c.addi4spn with zero immediate, c.addi16sp with zero immediate, c.lwsp to x0.
+        assert_eq!(expand_compressed(0x0004), None);
+        assert_eq!(expand_compressed(0x6101), None);
+        assert_eq!(expand_compressed(0x4002), None);
+        // c.jr x0
+        assert_eq!(expand_compressed(0x8002), None);
+        // c.fld fa0, 0(a0)
+        assert_eq!(expand_compressed(0x2108), None);
+        // 32-bit encoding
+        assert!(!is_compressed(0x0513));
+        assert_eq!(expand_compressed(0x0513), None);
+
+        assert_eq!(decode_compressed_instruction(0x0000), Instruction::unimpl());
+        assert_eq!(
+            decode_compressed_instruction(0x1575),
+            decode_instruction(0xffd50513)
+        );
+    }
+
+    #[test]
+    fn test_decode_parcels_until_end_of_a_block() {
+        // c.li a0, 5; addi a1, x0, 7; c.jr ra; c.li a0, 0
+        let (block, sizes) =
+            decode_parcels_until_end_of_a_block([0x4515, 0x0593, 0x0070, 0x8082, 0x4501]);
+        assert_eq!(sizes, [2, 4, 2]);
+        assert_eq!(block.0[0], decode_compressed_instruction(0x4515));
+        assert_eq!(block.0[1], decode_instruction(0x00700593));
+        assert_eq!(block.0[2], decode_compressed_instruction(0x8082));
+
+        // The upper parcel of the 32-bit instruction is missing.
+        let (block, sizes) = decode_parcels_until_end_of_a_block([0x4515, 0x0593]);
+        assert_eq!(sizes, [2]);
+        assert_eq!(block.0.len(), 1);
+    }
+}
@@ -57,6 +57,7 @@
 //! This module is particularly useful for tasks such as control flow analysis, optimization,
 //! and instruction-level parallelism detection in RISC-V programs.
 
+use crate::cpu::instructions::{amo_name, AMO_OPCODE};
 use crate::riscv::instructions::{BasicBlock, BasicBlockProgram, Instruction, InstructionDecoder};
 use nexus_common::riscv::{instruction::InstructionType, register::Register, Opcode};
 use rrs_lib::process_instruction;
@@ -156,6 +157,18 @@ pub fn decode_instruction(u32_instruction: u32) -> Instruction {
                 s_imm,
                 InstructionType::SType,
             )
+        } else if let Some(name) = (opcode == AMO_OPCODE && fn3 == 0b010)
+            .then(|| amo_name(fn7 >> 2))
+            .flatten()
+        {
+            // Atomic instructions are decoded so that executing them fails with a descriptive error.
+            Instruction::new(
+                Opcode::new(opcode, Some(fn3), Some(fn7), name),
+                Register::from(rd),
+                Register::from(rs1),
+                rs2.into(),
+                InstructionType::RType,
+            )
         } else if opcode == DYNAMIC_RTYPE_OPCODE {
             Instruction::new(
                 Opcode::new(opcode, Some(fn3), Some(fn7), "dynamic"),
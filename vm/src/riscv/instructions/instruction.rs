@@ -114,6 +114,17 @@ impl InstructionProcessor for InstructionDecoder {
         )
     }
 
+    // FENCE is a no-op, the predecessor and successor sets are kept in the immediate.
+    fn process_fence(&mut self, dec_insn: IType) -> Self::InstructionResult {
+        Instruction::new(
+            Opcode::from(BuiltinOpcode::FENCE),
+            Register::from(dec_insn.rd as u8),
+            Register::from(dec_insn.rs1 as u8),
+            dec_insn.imm as _,
+            InstructionType::IType,
+        )
+    }
+
     fn process_jalr(&mut self, dec_insn: IType) -> Self::InstructionResult {
         Instruction::new(
             Opcode::from(BuiltinOpcode::JALR),
@@ -131,7 +142,6 @@ impl InstructionProcessor for InstructionDecoder {
         process_csrrsi(dec_insn: ITypeCSR),
         process_csrrw(dec_insn: ITypeCSR),
         process_csrrwi(dec_insn: ITypeCSR),
-        process_fence(dec_insn: IType),
         process_mret(),
         process_wfi()
     }
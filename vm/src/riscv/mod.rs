@@ -1,6 +1,13 @@
+#[cfg(feature = "riscv-c")]
+pub(crate) mod compressed;
 pub(crate) mod decoder;
 pub(crate) mod instructions;
 
+#[cfg(feature = "riscv-c")]
+pub use compressed::{
+    decode_compressed_instruction, decode_parcels_until_end_of_a_block, expand_compressed,
+    is_compressed,
+};
 pub use decoder::{decode_instruction, decode_instructions, decode_until_end_of_a_block};
 pub use instructions::{
     BasicBlock, BasicBlockProgram, BuiltinOpcode, Instruction, InstructionType, Opcode,
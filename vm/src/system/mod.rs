@@ -1,3 +1,5 @@
 mod syscall;
+mod table;
 
 pub use syscall::{SyscallCode, SyscallInstruction};
+pub use table::{SyscallHandler, SyscallState, SyscallTable};
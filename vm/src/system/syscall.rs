@@ -206,6 +206,15 @@ impl SyscallInstruction {
             return Err(VMError::InvalidProfileLabel(label));
         }
 
+        #[cfg(feature = "profiler")]
+        if let Some(profiler) = executor.profiler.as_mut() {
+            if marker == "^" {
+                profiler.enter_region(fn_name);
+            } else {
+                profiler.exit_region(fn_name)?;
+            }
+        }
+
         // Get or create an entry in the cycle tracker for this function
         let entry = executor.cycle_tracker.entry(fn_name.to_string());
 
@@ -0,0 +1,184 @@
+//! Registry of syscalls recognized by the emulator.
+//!
+//! `SyscallTable` maps syscall codes (the value of register "a7" at an `ECALL`) to their
+//! implementation. Built-in syscalls are executed by [`SyscallInstruction`](super::SyscallInstruction),
+//! custom syscalls are executed by handlers registered with [`SyscallTable::register`].
+//!
+//! Custom handlers are only run by the emulator, they are not supported by the prover.
+use std::{collections::BTreeMap, fmt};
+
+use crate::{
+    cpu::Cpu,
+    error::{Result, VMError},
+    riscv::Register,
+};
+
+use super::SyscallCode;
+
+/// Handler of a custom syscall.
+pub type SyscallHandler = Box<dyn Fn(&mut SyscallState) -> Result<()>>;
+
+/// The view of the VM state given to a custom syscall handler.
+///
+/// Registers are read from the CPU state at the time of the `ECALL`. Similar to built-in syscalls,
+/// at most one register is written back once the handler returns successfully.
+pub struct SyscallState<'a> {
+    cpu: &'a Cpu,
+    result: Option<(Register, u32)>,
+}
+
+impl<'a> SyscallState<'a> {
+    pub(crate) fn new(cpu: &'a Cpu) -> Self {
+        Self { cpu, result: None }
+    }
+
+    /// Returns the program counter of the `ECALL` instruction.
+    pub fn pc(&self) -> u32 {
+        self.cpu.pc.value
+    }
+
+    /// Returns the value of `reg`.
+    pub fn read_register(&self, reg: Register) -> u32 {
+        self.cpu.registers[reg]
+    }
+
+    /// Returns the syscall argument `idx`, stored in registers "a0" through "a6".
+    ///
+    /// Panics if `idx` is greater than 6.
+    pub fn arg(&self, idx: usize) -> u32 {
+        assert!(idx < 7, "syscalls have at most 7 arguments");
+        self.read_register(Register::from(Register::X10 as u8 + idx as u8))
+    }
+
+    /// Sets the register written back after the syscall, overwriting the previous result if any.
+    pub fn write_result(&mut self, reg: Register, value: u32) {
+        self.result = Some((reg, value));
+    }
+
+    /// Returns the register and value to be written back.
+    pub fn result(&self) -> Option<(Register, u32)> {
+        self.result
+    }
+}
+
+enum SyscallEntry {
+    Builtin,
+    Custom(SyscallHandler),
+}
+
+/// Syscalls the emulator dispatches `ECALL` instructions to.
+///
+/// An `ECALL` with a code missing from the table fails with [`VMError::UnimplementedSyscall`].
+pub struct SyscallTable {
+    entries: BTreeMap<u32, SyscallEntry>,
+}
+
+impl SyscallTable {
+    /// Returns a table containing only the built-in syscalls.
+    pub fn standard() -> Self {
+        let entries = [
+            SyscallCode::Write,
+            SyscallCode::Exit,
+            SyscallCode::ReadFromPrivateInput,
+            SyscallCode::CycleCount,
+            SyscallCode::OverwriteStackPointer,
+            SyscallCode::OverwriteHeapPointer,
+        ]
+        .into_iter()
+        .map(|code| (u32::from(code), SyscallEntry::Builtin))
+        .collect();
+        Self { entries }
+    }
+
+    /// Registers a handler for the custom syscall `code`.
+    ///
+    /// Returns [`VMError::DuplicateSyscall`] if `code` is already registered.
+    pub fn register(
+        &mut self,
+        code: u32,
+        handler: impl Fn(&mut SyscallState) -> Result<()> + 'static,
+    ) -> Result<()> {
+        if self.entries.contains_key(&code) {
+            return Err(VMError::DuplicateSyscall(code));
+        }
+        self.entries
+            .insert(code, SyscallEntry::Custom(Box::new(handler)));
+        Ok(())
+    }
+
+    /// Returns `true` if `code` is registered, either as a built-in or a custom syscall.
+    pub fn contains(&self, code: u32) -> bool {
+        self.entries.contains_key(&code)
+    }
+
+    /// Returns `true` if `code` is a built-in syscall.
+    pub fn is_builtin(&self, code: u32) -> bool {
+        matches!(self.entries.get(&code), Some(SyscallEntry::Builtin))
+    }
+
+    /// Returns the handler of the custom syscall `code`.
+    pub fn custom_handler(&self, code: u32) -> Option<&SyscallHandler> {
+        match self.entries.get(&code) {
+            Some(SyscallEntry::Custom(handler)) => Some(handler),
+            _ => None,
+        }
+    }
+}
+
+impl Default for SyscallTable {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl fmt::Debug for SyscallTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.entries.iter().map(|(code, entry)| {
+                let kind = match entry {
+                    SyscallEntry::Builtin => "builtin",
+                    SyscallEntry::Custom(_) => "custom",
+                };
+                (format!("{code:#x}"), kind)
+            }))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nexus_common::cpu::Registers;
+
+    #[test]
+    fn test_register_syscall() {
+        let mut table = SyscallTable::standard();
+        assert!(table.is_builtin(SyscallCode::Exit.into()));
+        assert!(!table.contains(0x500));
+
+        table
+            .register(0x500, |state| {
+                state.write_result(Register::X10, state.arg(0) + state.arg(1));
+                Ok(())
+            })
+            .unwrap();
+        assert!(table.contains(0x500));
+        assert!(!table.is_builtin(0x500));
+
+        assert_eq!(
+            table.register(0x500, |_| Ok(())),
+            Err(VMError::DuplicateSyscall(0x500))
+        );
+        assert_eq!(
+            table.register(SyscallCode::Write.into(), |_| Ok(())),
+            Err(VMError::DuplicateSyscall(SyscallCode::Write.into()))
+        );
+
+        let mut cpu = Cpu::default();
+        cpu.registers.write(Register::X10, 2);
+        cpu.registers.write(Register::X11, 3);
+        let mut state = SyscallState::new(&cpu);
+        table.custom_handler(0x500).unwrap()(&mut state).unwrap();
+        assert_eq!(state.result(), Some((Register::X10, 5)));
+    }
+}
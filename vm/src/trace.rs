@@ -7,7 +7,6 @@ use crate::{
     error::{Result, VMError},
     memory::MemoryRecords,
     riscv::{BasicBlock, Instruction},
-    WORD_SIZE,
 };
 
 /// A program step.
@@ -239,9 +238,7 @@ fn k_step(
                 return (Some(block), Err(e));
             }
             Ok(basic_block_entry) => {
-                let at = (vm.get_executor().cpu.pc.value as usize
-                    - basic_block_entry.start as usize)
-                    / WORD_SIZE;
+                let at = basic_block_entry.index_of(vm.get_executor().cpu.pc.value);
 
                 for instruction in basic_block_entry.block.0[at..].iter() {
                     if block.steps.len() == k {
@@ -378,8 +375,7 @@ fn bb_step(vm: &mut impl Emulator) -> (Option<Block>, Result<()>) {
     match vm.fetch_block(vm.get_executor().cpu.pc.value) {
         Err(e) => return (None, Err(e)),
         Ok(basic_block_entry) => {
-            let at = (vm.get_executor().cpu.pc.value as usize - basic_block_entry.start as usize)
-                / WORD_SIZE;
+            let at = basic_block_entry.index_of(vm.get_executor().cpu.pc.value);
 
             for instruction in basic_block_entry.block.0[at..].iter() {
                 let pc = vm.get_executor().cpu.pc.value;
@@ -0,0 +1,44 @@
+#![cfg(feature = "profiler")]
+
+use nexus_vm::{
+    emulator::{Emulator, HarvardEmulator, Profiler},
+    error::VMError,
+    riscv::{BasicBlock, BuiltinOpcode, Instruction, Opcode},
+};
+
+#[test]
+fn test_profile_opcodes() {
+    let ir = |opcode, op_a, op_b, op_c| Instruction::new_ir(Opcode::from(opcode), op_a, op_b, op_c);
+    let basic_block = BasicBlock::new(vec![
+        ir(BuiltinOpcode::ADDI, 1, 0, 3),
+        ir(BuiltinOpcode::ADDI, 2, 0, 5),
+        ir(BuiltinOpcode::ADD, 3, 1, 2),
+        ir(BuiltinOpcode::ADD, 4, 3, 3),
+        ir(BuiltinOpcode::SUB, 5, 4, 1),
+        ir(BuiltinOpcode::ADD, 6, 5, 2),
+        // exit with code 0
+        ir(BuiltinOpcode::ADDI, 17, 0, 0x201),
+        ir(BuiltinOpcode::ECALL, 0, 0, 0),
+    ]);
+
+    let mut emulator = HarvardEmulator::from_basic_blocks(&vec![basic_block]);
+    emulator.executor.profiler = Some(Profiler::default());
+    assert_eq!(
+        emulator.execute(false).map(|_| ()),
+        Err(VMError::VMExited(0))
+    );
+
+    let profiler = emulator.executor.profiler.as_ref().unwrap();
+    assert_eq!(profiler.total_cycles(), 8);
+    assert_eq!(
+        profiler.report(),
+        vec![
+            (Opcode::from(BuiltinOpcode::ADD), 3),
+            (Opcode::from(BuiltinOpcode::ADDI), 3),
+            (Opcode::from(BuiltinOpcode::ECALL), 1),
+            (Opcode::from(BuiltinOpcode::SUB), 1),
+        ]
+    );
+    assert_eq!(profiler.fraction(&Opcode::from(BuiltinOpcode::ADD)), 0.375);
+    assert_eq!(profiler.fraction(&Opcode::from(BuiltinOpcode::MUL)), 0.0);
+}